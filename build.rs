@@ -1,8 +1,183 @@
 extern crate bindgen;
+extern crate cc;
 extern crate cpp_build;
+extern crate cpp_stl_build;
+extern crate syn;
 
 use std::env;
-use std::path::PathBuf;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Returns the year of a standard given as in `-std`, e.g. 2017 for `c++17`, 2020 for
+/// `gnu++2a` and 1998 for `c++03`, which only revised C++98.
+fn standard_year(standard: &str) -> u32 {
+    let version = standard
+        .strip_prefix("c++")
+        .or_else(|| standard.strip_prefix("gnu++"))
+        .unwrap_or_else(|| panic!("unknown C++ standard `{}`", standard));
+    match version {
+        "98" | "03" => 1998,
+        "11" | "0x" => 2011,
+        "14" | "1y" => 2014,
+        "17" | "1z" => 2017,
+        "20" | "2a" => 2020,
+        "23" | "2b" => 2023,
+        "26" | "2c" => 2026,
+        _ => panic!("unknown C++ standard `{}`", standard),
+    }
+}
+
+/// The standard to build with, which is `CPP_STL_STD` if set, e.g. `CPP_STL_STD=gnu++17`,
+/// or else the standard of the newest enabled `cxx*` feature.
+fn cxx_standard() -> String {
+    let required = if env::var_os("CARGO_FEATURE_CXX20").is_some() {
+        20
+    } else if env::var_os("CARGO_FEATURE_CXX17").is_some() {
+        17
+    } else if env::var_os("CARGO_FEATURE_CXX14").is_some() {
+        14
+    } else {
+        11
+    };
+
+    match env::var("CPP_STL_STD") {
+        Ok(standard) => {
+            assert!(
+                standard_year(&standard) >= 2000 + required,
+                "CPP_STL_STD={} is older than C++{}, which the enabled features need",
+                standard,
+                required
+            );
+            standard
+        }
+        Err(_) => format!("c++{}", required),
+    }
+}
+
+/// Fails on the features of headers which the standard lacks, e.g. `optional` without
+/// `cxx17`, rather than leaving their modules out.
+fn check_features() {
+    let year = standard_year(&cxx_standard());
+    for (feature, required) in [("optional", 2017), ("variant", 2017), ("span", 2020)] {
+        let var = format!("CARGO_FEATURE_{}", feature.to_uppercase());
+        assert!(
+            env::var_os(var).is_none() || year >= required,
+            "the `{}` feature needs C++{}, e.g. with the `cxx{}` feature or `CPP_STL_STD`",
+            feature,
+            required % 100,
+            required % 100
+        );
+    }
+}
+
+fn libcxx() -> bool {
+    env::var_os("CARGO_FEATURE_LIBCXX").is_some()
+}
+
+/// Flags selecting the standard library and its ABI, which bindgen, `cpp_build` and the
+/// C++ code of dependent crates have to agree on.
+fn stdlib_flags() -> Vec<&'static str> {
+    let old_abi = env::var_os("CARGO_FEATURE_OLD_ABI").is_some();
+    assert!(
+        !(libcxx() && old_abi),
+        "the `old_abi` feature selects an ABI of libstdc++, not of libc++"
+    );
+
+    let mut flags = Vec::new();
+    if libcxx() {
+        flags.push("-stdlib=libc++");
+    }
+    if old_abi {
+        flags.push("-D_GLIBCXX_USE_CXX11_ABI=0");
+    }
+    flags
+}
+
+/// The standard, the standard library and the whitespace separated flags of
+/// `CPP_STL_CXXFLAGS`.
+fn compiler_flags() -> Vec<String> {
+    let mut flags = vec![format!("-std={}", cxx_standard())];
+    flags.extend(stdlib_flags().into_iter().map(str::to_owned));
+    if let Ok(extra) = env::var("CPP_STL_CXXFLAGS") {
+        flags.extend(extra.split_whitespace().map(str::to_owned));
+    }
+    flags
+}
+
+/// The standard library the compiler uses with `compiler_flags()`, which is `libc++`,
+/// `libstdc++` or `msvc`.
+///
+/// It is found by preprocessing "csrc/probe_stdlib.cpp" rather than from the `libcxx`
+/// feature, as clang defaults to libc++ on some targets, e.g. apple and android.
+fn stdlib() -> &'static str {
+    static STDLIB: OnceLock<&'static str> = OnceLock::new();
+    STDLIB.get_or_init(|| {
+        let mut build = cc::Build::new();
+        build
+            .cpp(true)
+            .cargo_metadata(false)
+            .file("csrc/probe_stdlib.cpp");
+        for flag in compiler_flags() {
+            build.flag(&flag);
+        }
+        let output = String::from_utf8_lossy(&build.expand()).into_owned();
+        [
+            ("cpp_stl_stdlib_libcxx", "libc++"),
+            ("cpp_stl_stdlib_libstdcxx", "libstdc++"),
+            ("cpp_stl_stdlib_msvc", "msvc"),
+        ]
+        .iter()
+        .find(|(marker, _)| output.contains(marker))
+        .map(|(_, name)| *name)
+        .expect("Couldn't find out the standard library from csrc/probe_stdlib.cpp")
+    })
+}
+
+/// Flags which bindgen, `cpp_build` and dependent crates compile with, which are
+/// `compiler_flags()` and `-DCPP_STL_LIBCXX` if the standard library is libc++.
+fn cxx_flags() -> Vec<String> {
+    let mut flags = compiler_flags();
+    if stdlib() == "libc++" {
+        flags.push("-DCPP_STL_LIBCXX".to_owned());
+    }
+    flags
+}
+
+/// The cargo features selecting which headers are bound.
+const HEADERS: &[&str] = &[
+    "array",
+    "bitset",
+    "deque",
+    "forward_list",
+    "functional",
+    "list",
+    "map",
+    "memory",
+    "optional",
+    "queue",
+    "set",
+    "span",
+    "stack",
+    "string",
+    "tuple",
+    "unordered_map",
+    "unordered_set",
+    "variant",
+    "vector",
+];
+
+/// Defines `CPP_STL_HAS_<FEATURE>` for each enabled header, which "wrapper.hpp" declares
+/// the opaque types of.
+fn header_defines() -> Vec<String> {
+    HEADERS
+        .iter()
+        .map(|header| header.to_uppercase())
+        .filter(|header| env::var_os(format!("CARGO_FEATURE_{}", header)).is_some())
+        .map(|header| format!("-DCPP_STL_HAS_{}", header))
+        .collect()
+}
 
 fn import_stl() {
     use bindgen::*;
@@ -26,9 +201,12 @@ fn import_stl() {
         .header("csrc/wrapper.hpp")
         .clang_arg("-x")
         .clang_arg("c++")
-        .clang_arg("-std=c++11")
         // required to get cross compilation for aarch64 to work because of an issue in flatbuffers
         .clang_arg("-fms-extensions");
+    let bindings = cxx_flags()
+        .into_iter()
+        .chain(header_defines())
+        .fold(bindings, |bindings, flag| bindings.clang_arg(flag));
 
     let bindings = bindings.generate().expect("Unable to generate bindings");
 
@@ -39,18 +217,314 @@ fn import_stl() {
         .expect("Couldn't write bindings!");
 }
 
-fn build_inline_cpp() {
-    cpp_build::Config::new()
+/// The size and alignment of an opaque type of "wrapper.hpp", e.g. `rust::vector_of_bool`.
+struct Layout {
+    name: String,
+    size: usize,
+    align: usize,
+}
+
+fn out_dir() -> PathBuf {
+    PathBuf::from(env::var("OUT_DIR").unwrap())
+}
+
+/// The standard library the layouts of `layouts/` are recorded for.
+fn stdlib_name() -> &'static str {
+    match stdlib() {
+        "libstdc++" if env::var_os("CARGO_FEATURE_OLD_ABI").is_some() => "libstdc++-old-abi",
+        stdlib => stdlib,
+    }
+}
+
+/// The pre-generated layouts for the target, e.g.
+/// `layouts/x86_64-unknown-linux-gnu-libstdc++.txt`, which are used instead of bindgen
+/// unless `CPP_STL_LAYOUTS=bindgen`, so that building needs no libclang.
+fn prebuilt_layouts() -> Option<PathBuf> {
+    let path = Path::new("layouts").join(format!(
+        "{}-{}.txt",
+        env::var("TARGET").unwrap(),
+        stdlib_name()
+    ));
+    match env::var("CPP_STL_LAYOUTS").as_ref().map(String::as_str) {
+        Ok("bindgen") => None,
+        Ok("prebuilt") => {
+            assert!(path.is_file(), "{} doesn't exist", path.display());
+            Some(path)
+        }
+        Ok(mode) => panic!(
+            "CPP_STL_LAYOUTS={} is neither `prebuilt` nor `bindgen`",
+            mode
+        ),
+        Err(_) => Some(path).filter(|path| path.is_file()),
+    }
+}
+
+/// Reads lines of `name size align`, skipping blank lines and `#` comments.
+fn read_layouts(path: &Path) -> Vec<Layout> {
+    let source = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Couldn't read {}: {}", path.display(), e));
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let fields: Vec<_> = line.split_whitespace().collect();
+            match fields[..] {
+                [name, size, align] => Layout {
+                    name: name.to_owned(),
+                    size: size.parse().unwrap(),
+                    align: align.parse().unwrap(),
+                },
+                _ => panic!("{}: invalid layout `{}`", path.display(), line),
+            }
+        })
+        .collect()
+}
+
+/// Writes the opaque types as bindgen would into `$OUT_DIR/stl.rs`, and the checks of
+/// their C++ layouts into `$OUT_DIR/cpp_stl_layouts.hpp`.
+fn write_layouts(path: &Path, layouts: &[Layout]) {
+    let mut rust = String::new();
+    let mut cpp = format!("// Generated by build.rs from {}.\n\n", path.display());
+    cpp.push_str("#pragma once\n\n#include \"layouts.hpp\"\n\n");
+
+    writeln!(rust, "pub mod root {{").unwrap();
+    for namespace in &["std", "rust"] {
+        writeln!(rust, "    #[allow(non_camel_case_types)]").unwrap();
+        writeln!(rust, "    pub mod {} {{", namespace).unwrap();
+        let prefix = format!("{}::", namespace);
+        for layout in layouts {
+            let name = match layout.name.strip_prefix(&prefix) {
+                Some(name) => name,
+                None => continue,
+            };
+            let unit = layout.align.min(8);
+            assert!(
+                layout.size % unit == 0,
+                "{}: invalid layout of {}",
+                path.display(),
+                layout.name
+            );
+            writeln!(rust, "        #[repr(C)]").unwrap();
+            writeln!(rust, "        #[repr(align({}))]", layout.align).unwrap();
+            writeln!(rust, "        #[derive(Debug, Default, Copy, Clone)]").unwrap();
+            writeln!(rust, "        pub struct {} {{", name).unwrap();
+            writeln!(
+                rust,
+                "            pub _bindgen_opaque_blob: [u{}; {}],",
+                unit * 8,
+                layout.size / unit
+            )
+            .unwrap();
+            writeln!(rust, "        }}").unwrap();
+
+            // `std::string` is an alias, which can't be forward declared.
+            if *namespace == "rust" {
+                writeln!(cpp, "namespace rust {{ struct {}; }}", name).unwrap();
+            }
+            writeln!(
+                cpp,
+                "CPP_STL_CHECK_LAYOUT({}, {}, {});",
+                layout.name, layout.size, layout.align
+            )
+            .unwrap();
+        }
+        writeln!(rust, "    }}").unwrap();
+    }
+    writeln!(rust, "}}").unwrap();
+
+    fs::write(out_dir().join("stl.rs"), rust).unwrap();
+    fs::write(out_dir().join("cpp_stl_layouts.hpp"), cpp).unwrap();
+}
+
+/// Records the layouts of the opaque types generated by bindgen into
+/// `$OUT_DIR/layouts.txt`, which is how the files of `layouts/` are made, building with
+/// all features and `CPP_STL_LAYOUTS=bindgen`.
+/// Writes the size and alignment of each opaque struct of `items` to `layouts`, naming it
+/// after the innermost module, e.g. `rust::vector_of_bool`.
+fn record_items(namespace: &str, items: &[syn::Item], layouts: &mut String) {
+    for item in items {
+        match item {
+            syn::Item::Mod(module) => {
+                if let Some((_, items)) = &module.content {
+                    record_items(&module.ident.to_string(), items, layouts);
+                }
+            }
+            syn::Item::Struct(item) => {
+                let name = &item.ident;
+                let mut align = None;
+                for attr in item
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path().is_ident("repr"))
+                {
+                    attr.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("align") {
+                            let content;
+                            syn::parenthesized!(content in meta.input);
+                            align = Some(content.parse::<syn::LitInt>()?.base10_parse::<usize>()?);
+                        }
+                        Ok(())
+                    })
+                    .unwrap();
+                }
+
+                let blob = item
+                    .fields
+                    .iter()
+                    .find(|field| {
+                        field
+                            .ident
+                            .as_ref()
+                            .is_some_and(|ident| ident == "_bindgen_opaque_blob")
+                    })
+                    .unwrap_or_else(|| panic!("{} isn't opaque", name));
+                let (unit, count) = match &blob.ty {
+                    syn::Type::Array(syn::TypeArray {
+                        elem,
+                        len:
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Int(count),
+                                ..
+                            }),
+                        ..
+                    }) => {
+                        let unit = match &**elem {
+                            syn::Type::Path(path) => {
+                                path.path.get_ident().map(|ident| ident.to_string())
+                            }
+                            _ => None,
+                        };
+                        let unit = unit
+                            .as_deref()
+                            .and_then(|unit| unit.strip_prefix('u'))
+                            .and_then(|bits| bits.parse::<usize>().ok())
+                            .unwrap_or_else(|| panic!("{} has an opaque blob of {:?}", name, unit));
+                        (unit / 8, count.base10_parse::<usize>().unwrap())
+                    }
+                    _ => panic!("{} has an opaque blob which isn't an array", name),
+                };
+
+                writeln!(
+                    layouts,
+                    "{}::{} {} {}",
+                    namespace,
+                    name,
+                    unit * count,
+                    align.unwrap_or(unit)
+                )
+                .unwrap();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn record_layouts() {
+    let bindings = fs::read_to_string(out_dir().join("stl.rs")).unwrap();
+    let bindings = syn::parse_file(&bindings).unwrap();
+
+    let mut layouts = format!(
+        "# Layouts of the opaque types of \"wrapper.hpp\" for {} with {},\n\
+         # recorded by building with all features and `CPP_STL_LAYOUTS=bindgen`.\n",
+        env::var("TARGET").unwrap(),
+        stdlib_name()
+    );
+    record_items("", &bindings.items, &mut layouts);
+
+    fs::write(out_dir().join("layouts.txt"), layouts).unwrap();
+}
+
+fn build_inline_cpp(prebuilt: bool) {
+    let mut config = cpp_build::Config::new();
+    config
         .flag("-fPIC")
-        .flag("-std=c++11")
         .flag("-Wno-sign-compare")
         .include("csrc")
         .debug(true)
-        .opt_level(if cfg!(debug_assertions) { 0 } else { 2 })
+        .opt_level(if cfg!(debug_assertions) { 0 } else { 2 });
+    for flag in cxx_flags().into_iter().chain(header_defines()) {
+        config.flag(&flag);
+    }
+    if libcxx() {
+        // Also links libc++ instead of libstdc++.
+        config.cpp_set_stdlib(Some("c++"));
+    }
+    if prebuilt {
+        // Includes "cpp_stl_layouts.hpp" to check the layouts.
+        config.include(out_dir()).flag("-DCPP_STL_PREBUILT_LAYOUTS");
+    }
+    config.build("src/lib.rs");
+}
+
+fn mirror_layouts() {
+    let mirrors = cxx_flags()
+        .iter()
+        .chain(&header_defines())
+        .fold(cpp_stl_build::Mirrors::new(), |mirrors, flag| {
+            mirrors.flag(flag)
+        });
+    rustc_cfgs()
+        .into_iter()
+        .fold(mirrors, |mirrors, cfg| mirrors.cfg(cfg))
+        .include("csrc")
         .build("src/lib.rs");
 }
 
+// Passed to the build scripts of dependent crates as `DEP_CPP_STL_INCLUDE` and
+// `DEP_CPP_STL_CXXFLAGS`, which `cpp_stl_build` picks up.
+fn export_metadata() {
+    let include = env::current_dir().unwrap().join("csrc");
+    println!("cargo:include={}", include.display());
+    println!("cargo:cxxflags={}", cxx_flags().join(" "));
+    println!("cargo:rerun-if-env-changed=CPP_STL_STD");
+    println!("cargo:rerun-if-env-changed=CPP_STL_CXXFLAGS");
+    println!("cargo:rerun-if-env-changed=CPP_STL_LAYOUTS");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=csrc");
+    println!("cargo:rerun-if-changed=layouts");
+}
+
+// `cpp_stl_libcxx` selects the libc++ layouts of `std::tuple` and `std::variant`, which
+// the C++ code checks against `CPP_STL_LIBCXX`. `cpp_stl_cxx17` and `cpp_stl_cxx20` enable
+// the modules of headers which need those standards, whether selected by the `cxx*`
+// features or by `CPP_STL_STD`.
+fn rustc_cfgs() -> Vec<&'static str> {
+    let mut cfgs = Vec::new();
+    if stdlib() == "libc++" {
+        cfgs.push("cpp_stl_libcxx");
+    }
+    let year = standard_year(&cxx_standard());
+    if year >= 2017 {
+        cfgs.push("cpp_stl_cxx17");
+    }
+    if year >= 2020 {
+        cfgs.push("cpp_stl_cxx20");
+    }
+    cfgs
+}
+
+fn emit_cfgs() {
+    for cfg in ["cpp_stl_libcxx", "cpp_stl_cxx17", "cpp_stl_cxx20"] {
+        println!("cargo:rustc-check-cfg=cfg({})", cfg);
+    }
+    for cfg in rustc_cfgs() {
+        println!("cargo:rustc-cfg={}", cfg);
+    }
+}
+
 fn main() {
-    import_stl();
-    build_inline_cpp();
+    check_features();
+    let prebuilt = prebuilt_layouts();
+    match &prebuilt {
+        Some(path) => write_layouts(path, &read_layouts(path)),
+        None => {
+            import_stl();
+            record_layouts();
+        }
+    }
+    build_inline_cpp(prebuilt.is_some());
+    mirror_layouts();
+    emit_cfgs();
+    export_metadata();
 }
@@ -0,0 +1,101 @@
+use std::io;
+use std::path::Path;
+
+use bindgen::Builder;
+
+/// The STL types which the generated bindings refer to, declared in `root::std` in place
+/// of the blacklisted ones.
+///
+/// Defaulted template arguments are given defaults, as bindgen may or may not spell them
+/// out.
+const STD_MODULE: &str = "pub mod std {
+    pub type string = ::cpp_stl::string::String;
+    pub type allocator<T> = ::cpp_stl::mapping::Allocator<T>;
+    pub type vector<T, A = allocator<T>> = ::cpp_stl::mapping::Vector<T, A>;
+    pub type default_delete<T> = ::cpp_stl::mapping::DefaultDelete<T>;
+    pub type unique_ptr<T, D = default_delete<T>> = ::cpp_stl::mapping::UniquePtr<T, D>;
+    pub type pair<T1, T2> = ::cpp_stl::utility::Pair<T1, T2>;
+}";
+
+/// Generates Rust bindings of C++ headers whose STL members are bound to `cpp_stl`.
+///
+/// ```ignore
+/// cpp_stl_build::Generator::new()
+///     .header("csrc/types.hpp")
+///     .clang_arg("-std=c++11")
+///     .whitelist_type("struct_with_vectors")
+///     .generate(out_dir.join("types.rs"))
+///     .expect("Unable to generate bindings");
+/// ```
+///
+/// A member `std::vector<int32_t>` becomes `cpp_stl::vector::VectorOfI32`, `std::string`
+/// becomes `cpp_stl::string::String` and `std::unique_ptr<T>` becomes
+/// `cpp_stl::memory::UniquePtr<T>`. Every type in `std` is blacklisted, so other STL
+/// members have to be made opaque with `opaque_type`, or the bindings will not compile.
+///
+/// The bindings are generated with C++ namespaces enabled, under a `root` module.
+pub struct Generator {
+    builder: Builder,
+}
+
+impl Generator {
+    pub fn new() -> Self {
+        let builder = Builder::default()
+            .enable_cxx_namespaces()
+            .whitelist_recursively(false)
+            .blacklist_type("std::.*")
+            .derive_copy(false)
+            .derive_debug(false)
+            .module_raw_line("root", STD_MODULE)
+            .clang_arg("-x")
+            .clang_arg("c++");
+        let builder = crate::cxx_flags()
+            .into_iter()
+            .fold(builder, |builder, flag| builder.clang_arg(flag));
+        let builder = match crate::include_dir() {
+            Some(dir) => builder.clang_arg(format!("-I{}", dir.display())),
+            None => builder,
+        };
+        Generator { builder }
+    }
+
+    pub fn header<T: Into<String>>(self, header: T) -> Self {
+        self.map(|builder| builder.header(header))
+    }
+
+    pub fn clang_arg<T: Into<String>>(self, arg: T) -> Self {
+        self.map(|builder| builder.clang_arg(arg))
+    }
+
+    /// Generates bindings for the types matching `pattern`, which are not followed into
+    /// the types of their members.
+    pub fn whitelist_type<T: AsRef<str>>(self, pattern: T) -> Self {
+        self.map(|builder| builder.whitelist_type(pattern))
+    }
+
+    pub fn opaque_type<T: AsRef<str>>(self, pattern: T) -> Self {
+        self.map(|builder| builder.opaque_type(pattern))
+    }
+
+    /// Configures the underlying bindgen builder, e.g. to whitelist functions.
+    pub fn map<F: FnOnce(Builder) -> Builder>(self, f: F) -> Self {
+        Generator {
+            builder: f(self.builder),
+        }
+    }
+
+    /// Writes the bindings to `path`, to be included with `include!`.
+    pub fn generate<P: AsRef<Path>>(self, path: P) -> io::Result<()> {
+        let bindings = self
+            .builder
+            .generate()
+            .map_err(|()| io::Error::other("Unable to generate bindings"))?;
+        bindings.write_to_file(path)
+    }
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,37 @@
+//! Helpers for the build scripts of crates using `cpp_stl`.
+//!
+//! `Generator` runs bindgen over C++ headers and maps their STL members to the types of
+//! `cpp_stl`, and `Mirrors` records the C++ layouts checked by `#[derive(CppMirror)]`.
+//!
+//! Both add the include directory of `cpp_stl` and the flags it is built with, which
+//! select the standard, the standard library and its ABI, when the crate depends on
+//! `cpp_stl` directly. Other C++ code of the crate should be built with them too, as
+//! `cxx_flags()` and `include_dir()` return.
+
+extern crate bindgen;
+extern crate cc;
+extern crate object;
+extern crate proc_macro2;
+extern crate syn;
+
+mod generator;
+mod mirror;
+
+pub use crate::generator::Generator;
+pub use crate::mirror::Mirrors;
+
+use std::env;
+use std::path::PathBuf;
+
+/// The flags `cpp_stl` is built with, including `-std` and those selecting the standard
+/// library and its ABI.
+pub fn cxx_flags() -> Vec<String> {
+    env::var("DEP_CPP_STL_CXXFLAGS")
+        .map(|flags| flags.split_whitespace().map(str::to_owned).collect())
+        .unwrap_or_default()
+}
+
+/// The directory of "cpp_stl.hpp".
+pub fn include_dir() -> Option<PathBuf> {
+    env::var_os("DEP_CPP_STL_INCLUDE").map(PathBuf::from)
+}
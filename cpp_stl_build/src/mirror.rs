@@ -0,0 +1,377 @@
+use std::convert::TryInto;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use object::{Object, ObjectSection};
+use proc_macro2::{Delimiter, LineColumn, TokenTree};
+
+/// A struct deriving `CppMirror`, and the C++ type it mirrors.
+struct Mirror {
+    /// The path of the module relative to the crate root, e.g. `vector::tests`.
+    module: String,
+    name: String,
+    cpp_type: String,
+    fields: Vec<String>,
+}
+
+/// Whether the `#[cfg(...)]` attributes of `attrs` hold, or `None` if one of them cannot
+/// be evaluated.
+///
+/// Features and the cfgs of the target are taken from the environment cargo gives build
+/// scripts, and others from `cfgs`. `test` holds, as a build script doesn't know whether
+/// the tests are built.
+fn is_enabled(attrs: &[syn::Attribute], cfgs: &[String]) -> Option<bool> {
+    let mut enabled = true;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("cfg")) {
+        enabled &= evaluate(&attr.parse_args().ok()?, cfgs)?;
+    }
+    Some(enabled)
+}
+
+fn evaluate(predicate: &syn::Meta, cfgs: &[String]) -> Option<bool> {
+    let name = predicate.path().get_ident()?.to_string();
+    match predicate {
+        syn::Meta::Path(_) if name == "test" => Some(true),
+        syn::Meta::Path(_) => Some(
+            cfgs.contains(&name)
+                || env::var_os(format!("CARGO_CFG_{}", name.to_uppercase())).is_some(),
+        ),
+        syn::Meta::NameValue(syn::MetaNameValue {
+            value:
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }),
+            ..
+        }) => {
+            let value = value.value();
+            if name == "feature" {
+                let var = format!("CARGO_FEATURE_{}", value.to_uppercase().replace('-', "_"));
+                return Some(env::var_os(var).is_some());
+            }
+            let values = env::var(format!("CARGO_CFG_{}", name.to_uppercase())).unwrap_or_default();
+            Some(values.split(',').any(|v| v == value))
+        }
+        syn::Meta::List(list) => {
+            let predicates = list
+                .parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+                )
+                .ok()?;
+            let mut values = predicates.iter().map(|predicate| evaluate(predicate, cfgs));
+            match name.as_str() {
+                "all" => values.try_fold(true, |all, value| Some(all & value?)),
+                "any" => values.try_fold(false, |any, value| Some(any | value?)),
+                "not" if predicates.len() == 1 => values.next()?.map(|value| !value),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// What `Mirrors` takes from the sources of the crate.
+#[derive(Default)]
+struct Sources {
+    mirrors: Vec<Mirror>,
+    /// The `cpp! {{ ... }}` blocks, which declare the C++ types.
+    declarations: String,
+}
+
+/// Collects the mirrors and the `cpp!` blocks of `file` and of its modules, in the order
+/// of `cpp_build`: the blocks of a file come before those of its modules in other files.
+fn collect_file(file: &Path, dir: &Path, module: &str, cfgs: &[String], sources: &mut Sources) {
+    let source = fs::read_to_string(file)
+        .unwrap_or_else(|e| panic!("Couldn't read {}: {}", file.display(), e));
+    let items = syn::parse_file(&source)
+        .unwrap_or_else(|e| panic!("Couldn't parse {}: {}", file.display(), e))
+        .items;
+    collect_declarations(&items, file, &source, sources);
+    collect_mirrors(&items, dir, module, cfgs, sources);
+}
+
+fn collect_declarations(items: &[syn::Item], file: &Path, source: &str, sources: &mut Sources) {
+    for item in items {
+        match item {
+            syn::Item::Macro(item) if item.mac.path.is_ident("cpp") => {
+                let mut tokens = item.mac.tokens.clone().into_iter();
+                if let (Some(TokenTree::Group(group)), None) = (tokens.next(), tokens.next()) {
+                    if group.delimiter() == Delimiter::Brace {
+                        let start = group.span_open().end();
+                        let end = group.span_close().start();
+                        writeln!(
+                            sources.declarations,
+                            "#line {} \"{}\"\n{}",
+                            start.line,
+                            file.display(),
+                            &source[offset(source, start)..offset(source, end)]
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+            syn::Item::Mod(syn::ItemMod {
+                content: Some((_, items)),
+                ..
+            }) => collect_declarations(items, file, source, sources),
+            _ => {}
+        }
+    }
+}
+
+fn collect_mirrors(
+    items: &[syn::Item],
+    dir: &Path,
+    module: &str,
+    cfgs: &[String],
+    sources: &mut Sources,
+) {
+    for item in items {
+        match item {
+            syn::Item::Struct(item) => {
+                let cpp_type = item
+                    .attrs
+                    .iter()
+                    .find(|attr| attr.path().is_ident("cpp_mirror"))
+                    .and_then(|attr| attr.parse_args::<syn::LitStr>().ok());
+                if let Some(cpp_type) = cpp_type {
+                    sources.mirrors.push(Mirror {
+                        module: module.to_owned(),
+                        name: item.ident.to_string(),
+                        cpp_type: cpp_type.value(),
+                        fields: item
+                            .fields
+                            .iter()
+                            .filter(|field| {
+                                !field
+                                    .attrs
+                                    .iter()
+                                    .any(|attr| attr.path().is_ident("cpp_mirror"))
+                            })
+                            .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+                            .collect(),
+                    });
+                }
+            }
+            syn::Item::Mod(item) if is_enabled(&item.attrs, cfgs) == Some(true) => {
+                let dir = dir.join(item.ident.to_string());
+                let module = if module.is_empty() {
+                    item.ident.to_string()
+                } else {
+                    format!("{}::{}", module, item.ident)
+                };
+                match &item.content {
+                    Some((_, items)) => collect_mirrors(items, &dir, &module, cfgs, sources),
+                    None => {
+                        let file = dir.with_extension("rs");
+                        let file = if file.exists() {
+                            file
+                        } else {
+                            dir.join("mod.rs")
+                        };
+                        collect_file(&file, &dir, &module, cfgs, sources);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The byte offset of `location`, whose column counts characters.
+fn offset(source: &str, location: LineColumn) -> usize {
+    let line: usize = source
+        .split_inclusive('\n')
+        .take(location.line - 1)
+        .map(str::len)
+        .sum();
+    line + source[line..]
+        .char_indices()
+        .nth(location.column)
+        .map_or(source.len() - line, |(i, _)| i)
+}
+
+/// Records the C++ layouts of the structs deriving `CppMirror`, which the derive
+/// compares with their Rust layouts.
+///
+/// The C++ types are taken from the `cpp! {{ ... }}` blocks of the crate, which are
+/// compiled with the flags given to `cpp_build`, but without the closures.
+///
+/// ```ignore
+/// cpp_build::Config::new().flag("-std=c++11").build("src/lib.rs");
+/// cpp_stl_build::Mirrors::new().flag("-std=c++11").build("src/lib.rs");
+/// ```
+///
+/// The mirrors are collected from the modules of the crate, skipping those under a
+/// `#[cfg(...)]` which is disabled or cannot be evaluated, like a cfg the build script
+/// emits itself unless it is passed to `cfg`.
+pub struct Mirrors {
+    build: cc::Build,
+    cfgs: Vec<String>,
+}
+
+impl Mirrors {
+    pub fn new() -> Self {
+        let mut build = cc::Build::new();
+        for flag in crate::cxx_flags() {
+            build.flag(&flag);
+        }
+        if let Some(dir) = crate::include_dir() {
+            build.include(dir);
+        }
+        Mirrors {
+            build,
+            cfgs: Vec::new(),
+        }
+    }
+
+    pub fn flag(mut self, flag: &str) -> Self {
+        self.build.flag(flag);
+        self
+    }
+
+    pub fn include<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.build.include(dir);
+        self
+    }
+
+    /// Enables `#[cfg(name)]`, e.g. for a `cargo:rustc-cfg` of the build script.
+    pub fn cfg(mut self, name: &str) -> Self {
+        self.cfgs.push(name.to_owned());
+        self
+    }
+
+    pub fn build<P: AsRef<Path>>(self, crate_root: P) {
+        record_layouts(&self.build, &self.cfgs, crate_root.as_ref());
+    }
+}
+
+impl Default for Mirrors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compiles the sizes and offsets into a section of an object, and reads them back from
+/// it, so that nothing has to run on the target.
+///
+/// The probe is built without LTO, whose objects hold no data until they are linked.
+fn record_layouts(build: &cc::Build, cfgs: &[String], crate_root: &Path) {
+    const SECTION: &str = "cpp_stl_mirror";
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let mut sources = Sources::default();
+    collect_file(
+        crate_root,
+        crate_root.parent().unwrap(),
+        "",
+        cfgs,
+        &mut sources,
+    );
+    let mirrors = sources.mirrors;
+
+    let mut layouts = String::new();
+    if !mirrors.is_empty() {
+        let mut probe = format!(
+            "#include <cstddef>\n#include <cstdint>\n\n{}\n#line 1 \"cpp_stl_mirror.cpp\"\n",
+            sources.declarations
+        );
+        let mut values = Vec::new();
+        for (i, mirror) in mirrors.iter().enumerate() {
+            writeln!(probe, "typedef {} cpp_stl_mirror_{};", mirror.cpp_type, i).unwrap();
+            values.push(format!("sizeof(cpp_stl_mirror_{})", i));
+            values.push(format!("alignof(cpp_stl_mirror_{})", i));
+            for field in &mirror.fields {
+                values.push(format!("offsetof(cpp_stl_mirror_{}, {})", i, field));
+            }
+        }
+        writeln!(
+            probe,
+            "\n#ifdef __APPLE__\n\
+             #define CPP_STL_MIRROR_SECTION \"__DATA,__{section}\"\n\
+             #else\n\
+             #define CPP_STL_MIRROR_SECTION \".{section}\"\n\
+             #endif\n\n\
+             extern \"C\" __attribute__((used, section(CPP_STL_MIRROR_SECTION)))\n\
+             const uint64_t cpp_stl_mirror_layouts[] = {{{values}}};",
+            section = SECTION,
+            values = values.join(", ")
+        )
+        .unwrap();
+
+        let probe_path = out_dir.join("cpp_stl_mirror.cpp");
+        let object_path = out_dir.join("cpp_stl_mirror.o");
+        fs::write(&probe_path, probe).expect("Couldn't write the mirror probe!");
+
+        // `cc` appends `CXXFLAGS` last, so the LTO flags are removed from its command.
+        let compiler = build
+            .clone()
+            .cpp(true)
+            .flag("-Wno-invalid-offsetof")
+            .get_compiler();
+        let status = Command::new(compiler.path())
+            .args(
+                compiler
+                    .args()
+                    .iter()
+                    .filter(|arg| !arg.to_string_lossy().starts_with("-flto")),
+            )
+            .arg("-fno-lto")
+            .arg("-c")
+            .arg(&probe_path)
+            .arg("-o")
+            .arg(&object_path)
+            .envs(compiler.get_envs())
+            .status()
+            .expect("Couldn't run the C++ compiler!");
+        assert!(status.success(), "Couldn't compile the mirror probe!");
+
+        let object = fs::read(&object_path).unwrap();
+        let object = object::File::parse(&*object).expect("Couldn't parse the mirror probe!");
+        let data = object
+            .sections()
+            .find(|section| {
+                section
+                    .name()
+                    .is_ok_and(|name| name.trim_start_matches(['.', '_']) == SECTION)
+            })
+            .and_then(|section| section.data().ok())
+            .expect("Couldn't find the mirror layouts in the probe!");
+        assert_eq!(data.len(), 8 * values.len());
+        let mut values = data.chunks(8).map(|bytes| {
+            let bytes = bytes.try_into().unwrap();
+            if object.is_little_endian() {
+                u64::from_le_bytes(bytes)
+            } else {
+                u64::from_be_bytes(bytes)
+            }
+        });
+
+        for mirror in &mirrors {
+            let size = values.next().unwrap();
+            let align = values.next().unwrap();
+            let offsets: Vec<_> = mirror
+                .fields
+                .iter()
+                .map(|field| format!("{}={}", field, values.next().unwrap()))
+                .collect();
+            writeln!(
+                layouts,
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                mirror.module,
+                mirror.name,
+                mirror.cpp_type,
+                size,
+                align,
+                offsets.join(" ")
+            )
+            .unwrap();
+        }
+    }
+
+    fs::write(out_dir.join("cpp_stl_mirror.txt"), layouts)
+        .expect("Couldn't write the mirror layouts!");
+}
@@ -0,0 +1,247 @@
+//! Derive macros for `cpp_stl`.
+
+extern crate proc_macro;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span};
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, LitStr};
+
+/// The file in `OUT_DIR` where `cpp_stl_build::Mirrors` records the C++ layouts.
+const LAYOUTS_FILE: &str = "cpp_stl_mirror.txt";
+
+/// Checks at build time that a `#[repr(C)]` struct has the layout of a C++ type.
+///
+/// ```ignore
+/// #[derive(CppMirror)]
+/// #[cpp_mirror("class_with_unique_ptr")]
+/// #[repr(C)]
+/// struct ClassWithUniquePtr {
+///     desc: StlString,
+///     value: UniquePtr<i32>,
+/// }
+/// ```
+///
+/// The size, the alignment and the offset of every field, which must be named as the
+/// C++ member, are compared with what the C++ compiler reports. Fields without a C++
+/// member, such as one standing for a base class, are marked `#[cpp_mirror(skip)]`.
+///
+/// The C++ layouts are recorded by `cpp_stl_build::Mirrors` in the build script of the
+/// crate, which finds the mirrors written in its sources, so the derive cannot be applied
+/// to structs generated by macros.
+#[proc_macro_derive(CppMirror, attributes(cpp_mirror))]
+pub fn derive_cpp_mirror(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Layout {
+    size: usize,
+    align: usize,
+    offsets: Vec<(String, usize)>,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let cpp_type = cpp_type(input)?;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "CppMirror cannot be derived for generic structs",
+        ));
+    }
+    if !is_repr_c(&input.attrs) {
+        return Err(syn::Error::new_spanned(
+            name,
+            "CppMirror requires #[repr(C)]",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "CppMirror requires named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "CppMirror can only be derived for structs",
+            ))
+        }
+    };
+
+    let layouts = find_layouts(&name.to_string(), &cpp_type.value());
+    if layouts.is_empty() {
+        return Err(syn::Error::new_spanned(
+            name,
+            "the C++ layout of this mirror is not recorded by `cpp_stl_build::Mirrors`; \
+             only mirrors written directly in the crate's sources are collected",
+        ));
+    }
+
+    let size_msg = format!(
+        "size of `{}` differs from sizeof({})",
+        name,
+        cpp_type.value()
+    );
+    let align_msg = format!(
+        "alignment of `{}` differs from alignof({})",
+        name,
+        cpp_type.value()
+    );
+    let missing_msg = format!(
+        "the C++ layout of `{}` is not recorded for its module by `cpp_stl_build::Mirrors`",
+        name
+    );
+
+    // Mirrors of the same name in other modules are told apart by `module_path!()`.
+    let mut module_checks = Vec::new();
+    for (module, layout) in &layouts {
+        let size = Literal::usize_unsuffixed(layout.size);
+        let align = Literal::usize_unsuffixed(layout.align);
+        let mut offset_checks = Vec::new();
+        for field in fields {
+            if is_skipped(&field.attrs)? {
+                continue;
+            }
+            let ident = field.ident.as_ref().unwrap();
+            let check = match layout.offsets.iter().find(|(member, _)| ident == member) {
+                Some((_, offset)) => {
+                    let offset = Literal::usize_unsuffixed(*offset);
+                    let msg = format!(
+                        "offset of `{}::{}` differs from offsetof({}, {})",
+                        name,
+                        ident,
+                        cpp_type.value(),
+                        ident
+                    );
+                    quote! {
+                        assert!(::core::mem::offset_of!(#name, #ident) == #offset, #msg);
+                    }
+                }
+                None if layouts.len() == 1 => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "the offset of this field is not recorded",
+                    ))
+                }
+                None => {
+                    let msg = format!("the offset of `{}::{}` is not recorded", name, ident);
+                    quote! { panic!(#msg); }
+                }
+            };
+            offset_checks.push(check);
+        }
+        module_checks.push(quote! {
+            if ::cpp_stl::mirror::is_module(module_path!(), #module) {
+                recorded = true;
+                assert!(::core::mem::size_of::<#name>() == #size, #size_msg);
+                assert!(::core::mem::align_of::<#name>() == #align, #align_msg);
+                #(#offset_checks)*
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl ::cpp_stl::mirror::CppMirror for #name {
+            const CPP_TYPE: &'static str = #cpp_type;
+        }
+
+        const _: () = {
+            let mut recorded = false;
+            #(#module_checks)*
+            assert!(recorded, #missing_msg);
+        };
+    })
+}
+
+fn cpp_type(input: &DeriveInput) -> syn::Result<LitStr> {
+    input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("cpp_mirror"))
+        .ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                "CppMirror requires the C++ type, e.g. #[cpp_mirror(\"class_name\")]",
+            )
+        })?
+        .parse_args()
+}
+
+fn is_repr_c(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let mut found = false;
+        if attr.path().is_ident("repr") {
+            let _ = attr.parse_nested_meta(|meta| {
+                found |= meta.path.is_ident("C");
+                Ok(())
+            });
+        }
+        found
+    })
+}
+
+fn is_skipped(attrs: &[Attribute]) -> syn::Result<bool> {
+    for attr in attrs {
+        if attr.path().is_ident("cpp_mirror") {
+            let arg: Ident = attr.parse_args()?;
+            if arg != "skip" {
+                return Err(syn::Error::new_spanned(arg, "expected `skip`"));
+            }
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Each line of the layouts file is `<module>\t<struct>\t<C++ type>\t<size>\t<align>\t<offsets>`,
+// where the module is relative to the crate root and the offsets are space separated
+// `<field>=<offset>`.
+fn find_layouts(name: &str, cpp_type: &str) -> Vec<(String, Layout)> {
+    let layouts = env::var_os("OUT_DIR")
+        .map(|dir| PathBuf::from(dir).join(LAYOUTS_FILE))
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    layouts
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split('\t');
+            let module = columns.next()?;
+            if columns.next()? != name || columns.next()? != cpp_type {
+                return None;
+            }
+            let size = columns.next()?.parse().ok()?;
+            let align = columns.next()?.parse().ok()?;
+            let offsets = columns
+                .next()
+                .unwrap_or("")
+                .split_whitespace()
+                .map(|offset| {
+                    let (field, offset) = offset.split_once('=')?;
+                    Some((field.to_owned(), offset.parse().ok()?))
+                })
+                .collect::<Option<_>>()?;
+            Some((
+                module.to_owned(),
+                Layout {
+                    size,
+                    align,
+                    offsets,
+                },
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,46 @@
+extern crate clang_sys;
+extern crate cpp_build;
+extern crate cpp_stl_build;
+
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=csrc");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rustc-check-cfg=cfg(no_libclang)");
+
+    let mut config = cpp_build::Config::new();
+    config.include("csrc");
+    if let Some(dir) = cpp_stl_build::include_dir() {
+        config.include(dir);
+    }
+    for flag in cpp_stl_build::cxx_flags() {
+        config.flag(&flag);
+    }
+    config.build("src/lib.rs");
+
+    // bindgen loads libclang when it runs, so the bindings are left out without it, but
+    // not on CI, which has to test them.
+    println!("cargo:rerun-if-env-changed=CI");
+    if clang_sys::load().is_err() {
+        assert!(
+            env::var_os("CI").is_none(),
+            "libclang is not found, which CI needs to test the generated bindings"
+        );
+        println!("cargo:warning=libclang is not found, so the generated bindings are not tested");
+        println!("cargo:rustc-cfg=no_libclang");
+        return;
+    }
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    cpp_stl_build::Generator::new()
+        .header("csrc/types.hpp")
+        .whitelist_type("name")
+        .whitelist_type("superclass")
+        .whitelist_type("subclass")
+        .whitelist_type("struct_with_vectors")
+        .map(|builder| builder.layout_tests(false))
+        .generate(out_dir.join("types.rs"))
+        .expect("Unable to generate bindings");
+}
@@ -0,0 +1,31 @@
+#pragma once
+
+#include <cstdint>
+#include <memory>
+#include <string>
+#include <utility>
+#include <vector>
+
+struct name {
+  std::string first_name;
+  std::string last_name;
+};
+
+struct superclass {
+  int32_t dummy;
+};
+
+struct subclass : superclass {
+  int32_t value;
+  std::string desc;
+
+  subclass(int32_t v, const char *s) : value(v), desc(s) {}
+};
+
+struct struct_with_vectors {
+  std::vector<int32_t> ids;
+  std::vector<std::unique_ptr<name>> names;
+  std::vector<std::pair<int32_t, float>> weights;
+  std::vector<std::unique_ptr<std::string>> cities;
+  std::vector<std::unique_ptr<subclass>> derived;
+};
@@ -0,0 +1,51 @@
+//! Binds the C++ types of "csrc/types.hpp" with `cpp_stl_build::Generator`, which maps
+//! their STL members to `cpp_stl`.
+//!
+//! The bindings are generated only where libclang is found.
+
+#[macro_use]
+extern crate cpp;
+extern crate cpp_stl;
+
+cpp! {{
+    #include "cpp_stl.hpp"
+    #include "types.hpp"
+}}
+
+#[cfg(not(no_libclang))]
+#[allow(
+    non_camel_case_types,
+    non_snake_case,
+    non_upper_case_globals,
+    dead_code
+)]
+#[allow(clippy::type_complexity)]
+pub mod types {
+    include!(concat!(env!("OUT_DIR"), "/types.rs"));
+}
+
+#[cfg(all(test, not(no_libclang)))]
+mod tests {
+    use crate::types::root;
+    use cpp_stl::vector::VectorSlice;
+
+    #[test]
+    fn unittest_generator() {
+        let x = unsafe {
+            cpp!([] -> &root::struct_with_vectors as "struct_with_vectors*" {
+                static struct_with_vectors x{{1, 2}, {}, {{3, 0.5f}}, {}, {}};
+                x.names.emplace_back(new name{"boncheol", "gu"});
+                x.cities.emplace_back(new std::string("seoul"));
+                x.derived.emplace_back(new subclass(7, "derived"));
+                return &x;
+            })
+        };
+
+        assert_eq!(x.ids.as_slice(), &[1, 2]);
+        assert_eq!(<(i32, f32)>::from(x.weights[0]), (3, 0.5));
+        assert_eq!(x.names[0].last_name.to_string(), "gu");
+        assert_eq!(x.cities[0].to_string(), "seoul");
+        assert_eq!(x.derived[0].value, 7);
+        assert_eq!(x.derived[0].desc.to_string(), "derived");
+    }
+}
@@ -0,0 +1,25 @@
+#pragma once
+
+// Included by the C++ code of crates which pass standard library objects to cpp_stl.
+//
+// Each translation unit references the symbol cpp_stl defines for the libstdc++ ABI it
+// is built with, so mixing the pre-C++11 and the C++11 ABI fails to link instead of
+// corrupting memory.
+
+#include <cstddef>
+
+#if defined(__GLIBCXX__)
+#if _GLIBCXX_USE_CXX11_ABI
+extern "C" const char cpp_stl_abi_cxx11;
+#define CPP_STL_ABI_TAG cpp_stl_abi_cxx11
+#else
+extern "C" const char cpp_stl_abi_old;
+#define CPP_STL_ABI_TAG cpp_stl_abi_old
+#endif
+
+namespace {
+__attribute__((used)) const char *const cpp_stl_abi_check = &CPP_STL_ABI_TAG;
+}
+
+#undef CPP_STL_ABI_TAG
+#endif
@@ -0,0 +1,33 @@
+#pragma once
+
+// Checks the layouts `build.rs` takes from `layouts/` instead of running bindgen. The
+// generated "cpp_stl_layouts.hpp" forward declares each type of "wrapper.hpp" it lists
+// and checks it with CPP_STL_CHECK_LAYOUT, which passes for the types of disabled
+// features, as they stay incomplete.
+
+#include <cstddef>
+#include <string>
+#include <type_traits>
+
+#include "wrapper.hpp"
+
+namespace cpp_stl_layouts {
+template <typename T, typename = void> struct is_complete : std::false_type {};
+
+template <typename T>
+struct is_complete<T, decltype(void(sizeof(T)))> : std::true_type {};
+
+template <typename T, size_t Size, size_t Align,
+          bool = is_complete<T>::value>
+struct matches : std::true_type {};
+
+template <typename T, size_t Size, size_t Align>
+struct matches<T, Size, Align, true>
+    : std::integral_constant<bool, sizeof(T) == Size && alignof(T) == Align> {
+};
+} // namespace cpp_stl_layouts
+
+#define CPP_STL_CHECK_LAYOUT(T, SIZE, ALIGN)                                   \
+  static_assert(::cpp_stl_layouts::matches<T, SIZE, ALIGN>::value,             \
+                "the layout of " #T " differs from layouts/, build with "      \
+                "CPP_STL_LAYOUTS=bindgen instead")
@@ -0,0 +1,11 @@
+// Preprocessed by `build.rs` to find out which standard library the compiler uses.
+
+#include <cstddef>
+
+#if defined(_LIBCPP_VERSION)
+cpp_stl_stdlib_libcxx
+#elif defined(__GLIBCXX__)
+cpp_stl_stdlib_libstdcxx
+#elif defined(_MSVC_STL_VERSION) || defined(_CPPLIB_VER)
+cpp_stl_stdlib_msvc
+#endif
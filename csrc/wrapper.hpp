@@ -1,15 +1,135 @@
+#pragma once
+
+// Each part is declared only if `build.rs` defines `CPP_STL_HAS_<FEATURE>` for the
+// matching cargo feature, so that only the enabled headers are parsed and compiled.
+
+#include <cstdint>
+
+#if defined(CPP_STL_HAS_ARRAY)
+#include <array>
+#endif
+
+#if defined(CPP_STL_HAS_BITSET)
+#include <bitset>
+#endif
+
+#if defined(CPP_STL_HAS_DEQUE)
+#include <deque>
+#endif
+
+#if defined(CPP_STL_HAS_FORWARD_LIST)
+#include <forward_list>
+#endif
+
+#if defined(CPP_STL_HAS_FUNCTIONAL)
+#include <functional>
+#endif
+
+#if defined(CPP_STL_HAS_LIST)
+#include <list>
+#endif
+
+#if defined(CPP_STL_HAS_MAP)
+#include <map>
+#endif
+
+#if defined(CPP_STL_HAS_MEMORY)
 #include <memory>
+#endif
+
+#if defined(CPP_STL_HAS_QUEUE)
+#include <queue>
+#endif
+
+#if defined(CPP_STL_HAS_SET)
+#include <set>
+#endif
+
+#if defined(CPP_STL_HAS_STRING)
 #include <string>
+#endif
+
+#if defined(CPP_STL_HAS_TUPLE)
+#include <tuple>
+#endif
+
+#if defined(CPP_STL_HAS_UNORDERED_MAP)
+#include <unordered_map>
+#endif
+
+#if defined(CPP_STL_HAS_UNORDERED_SET)
+#include <unordered_set>
+#endif
+
+#if defined(CPP_STL_HAS_VECTOR)
+#include <utility>
 #include <vector>
+#endif
+
+#if __cplusplus >= 201703L && defined(CPP_STL_HAS_OPTIONAL)
+#include <optional>
+#endif
+
+#if __cplusplus >= 202002L && defined(CPP_STL_HAS_SPAN)
+#include <span>
+#endif
 
 #define VECTOR_OF(T)                                                           \
   struct alignas(alignof(std::vector<T>)) vector_of_##T {                      \
     uint8_t payload[sizeof(std::vector<T>)];                                   \
   }
 
+#if defined(CPP_STL_HAS_MEMORY)
 using dummy_unique_ptr = std::unique_ptr<void>;
+#endif
+
+#if defined(CPP_STL_HAS_VECTOR)
+using dummy_vector = std::vector<int32_t>;
+using pair_of_int32_t_float = std::pair<int32_t, float>;
+#endif
+
+#if defined(CPP_STL_HAS_VECTOR) && defined(CPP_STL_HAS_TUPLE)
+using tuple_of_int32_t_float_double = std::tuple<int32_t, float, double>;
+#endif
+
+#if defined(CPP_STL_HAS_MAP)
+using dummy_map = std::map<int32_t, int32_t>;
+#endif
+
+#if defined(CPP_STL_HAS_UNORDERED_MAP)
+using dummy_unordered_map = std::unordered_map<int32_t, int32_t>;
+#endif
+
+#if defined(CPP_STL_HAS_SET)
+using dummy_set = std::set<int32_t>;
+#endif
+
+#if defined(CPP_STL_HAS_UNORDERED_SET)
+using dummy_unordered_set = std::unordered_set<int32_t>;
+#endif
+
+#if defined(CPP_STL_HAS_DEQUE)
+using dummy_deque = std::deque<int64_t>;
+#endif
+
+#if defined(CPP_STL_HAS_LIST)
+using dummy_list = std::list<int32_t>;
+#endif
+
+#if defined(CPP_STL_HAS_FORWARD_LIST)
+using dummy_forward_list = std::forward_list<int32_t>;
+#endif
+
+#if defined(CPP_STL_HAS_QUEUE)
+using dummy_priority_queue = std::priority_queue<int32_t>;
+#endif
+
+#if defined(CPP_STL_HAS_FUNCTIONAL)
+using dummy_function = std::function<void()>;
+#endif
 
 namespace rust {
+#if defined(CPP_STL_HAS_VECTOR)
 VECTOR_OF(bool);
 VECTOR_OF(uint8_t);
 VECTOR_OF(uint16_t);
@@ -21,8 +141,141 @@ VECTOR_OF(int32_t);
 VECTOR_OF(int64_t);
 VECTOR_OF(float);
 VECTOR_OF(dummy_unique_ptr);
+VECTOR_OF(pair_of_int32_t_float);
+#if defined(CPP_STL_HAS_TUPLE)
+VECTOR_OF(tuple_of_int32_t_float_double);
+#endif
+VECTOR_OF(dummy_vector);
 
+struct alignas(alignof(std::vector<std::string>)) vector_of_string {
+  uint8_t payload[sizeof(std::vector<std::string>)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_MEMORY)
 struct alignas(alignof(std::unique_ptr<void>)) unique_ptr_of_void {
   uint8_t payload[sizeof(std::unique_ptr<void>)];
 };
+
+struct alignas(alignof(std::unique_ptr<uint8_t[]>)) unique_ptr_of_array {
+  uint8_t payload[sizeof(std::unique_ptr<uint8_t[]>)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_MAP)
+struct alignas(alignof(dummy_map)) map_of_dummy {
+  uint8_t payload[sizeof(dummy_map)];
+};
+
+struct alignas(alignof(dummy_map::iterator)) map_iterator_of_dummy {
+  uint8_t payload[sizeof(dummy_map::iterator)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_UNORDERED_MAP)
+struct alignas(alignof(dummy_unordered_map)) unordered_map_of_dummy {
+  uint8_t payload[sizeof(dummy_unordered_map)];
+};
+
+struct alignas(alignof(dummy_unordered_map::iterator))
+    unordered_map_iterator_of_dummy {
+  uint8_t payload[sizeof(dummy_unordered_map::iterator)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_SET)
+struct alignas(alignof(dummy_set)) set_of_dummy {
+  uint8_t payload[sizeof(dummy_set)];
+};
+
+struct alignas(alignof(dummy_set::iterator)) set_iterator_of_dummy {
+  uint8_t payload[sizeof(dummy_set::iterator)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_UNORDERED_SET)
+struct alignas(alignof(dummy_unordered_set)) unordered_set_of_dummy {
+  uint8_t payload[sizeof(dummy_unordered_set)];
+};
+
+struct alignas(alignof(dummy_unordered_set::iterator))
+    unordered_set_iterator_of_dummy {
+  uint8_t payload[sizeof(dummy_unordered_set::iterator)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_DEQUE)
+struct alignas(alignof(dummy_deque)) deque_of_dummy {
+  uint8_t payload[sizeof(dummy_deque)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_LIST)
+struct alignas(alignof(dummy_list)) list_of_dummy {
+  uint8_t payload[sizeof(dummy_list)];
+};
+
+struct alignas(alignof(dummy_list::iterator)) list_iterator_of_dummy {
+  uint8_t payload[sizeof(dummy_list::iterator)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_FORWARD_LIST)
+struct alignas(alignof(dummy_forward_list)) forward_list_of_dummy {
+  uint8_t payload[sizeof(dummy_forward_list)];
+};
+
+struct alignas(alignof(dummy_forward_list::iterator))
+    forward_list_iterator_of_dummy {
+  uint8_t payload[sizeof(dummy_forward_list::iterator)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_QUEUE)
+struct alignas(alignof(dummy_priority_queue)) priority_queue_of_dummy {
+  uint8_t payload[sizeof(dummy_priority_queue)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_ARRAY)
+struct alignas(alignof(std::array<float, 16>)) array_of_float_16 {
+  uint8_t payload[sizeof(std::array<float, 16>)];
+};
+
+struct alignas(alignof(std::array<uint8_t, 32>)) array_of_uint8_t_32 {
+  uint8_t payload[sizeof(std::array<uint8_t, 32>)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_BITSET)
+struct alignas(alignof(std::bitset<64>)) bitset_64 {
+  uint8_t payload[sizeof(std::bitset<64>)];
+};
+
+struct alignas(alignof(std::bitset<256>)) bitset_256 {
+  uint8_t payload[sizeof(std::bitset<256>)];
+};
+#endif
+
+#if defined(CPP_STL_HAS_FUNCTIONAL)
+struct alignas(alignof(dummy_function)) function_of_dummy {
+  uint8_t payload[sizeof(dummy_function)];
+};
+#endif
+
+#if __cplusplus >= 201703L && defined(CPP_STL_HAS_OPTIONAL)
+struct alignas(alignof(std::optional<int64_t>)) optional_of_int64_t {
+  uint8_t payload[sizeof(std::optional<int64_t>)];
+};
+
+struct alignas(alignof(std::optional<std::string>)) optional_of_string {
+  uint8_t payload[sizeof(std::optional<std::string>)];
+};
+#endif
+
+#if __cplusplus >= 202002L && defined(CPP_STL_HAS_SPAN)
+struct alignas(alignof(std::span<float>)) span_of_float {
+  uint8_t payload[sizeof(std::span<float>)];
+};
+#endif
 } // namespace rust
@@ -0,0 +1,161 @@
+use std::borrow::{Borrow, BorrowMut};
+use std::ops::{Deref, DerefMut};
+
+cpp! {{
+    #include <array>
+    #include <cstddef>
+
+    static_assert(sizeof(std::array<float, 16>) == sizeof(float[16]), "");
+    static_assert(alignof(std::array<float, 16>) == alignof(float[16]), "");
+    static_assert(sizeof(std::array<uint8_t, 32>) == sizeof(uint8_t[32]), "");
+    static_assert(alignof(std::array<uint8_t, 32>) == alignof(uint8_t[32]), "");
+    static_assert(sizeof(std::array<float, 0>) != 0, "");
+
+    struct struct_with_arrays {
+        std::array<float, 16> transform;
+        std::array<uint8_t, 32> digest;
+        int32_t tag;
+    };
+}}
+
+/// `std::array<T, N>`, which is laid out exactly as `T[N]` for `N > 0`.
+///
+/// `std::array<T, 0>` takes a byte unlike `[T; 0]`, so an empty array fails to build.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Array<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> Array<T, N> {
+    const NOT_EMPTY: () = assert!(N > 0, "std::array<T, 0> is not laid out as [T; 0]");
+
+    pub fn new(values: [T; N]) -> Self {
+        let () = Self::NOT_EMPTY;
+        Array(values)
+    }
+
+    pub fn into_inner(self) -> [T; N] {
+        self.0
+    }
+}
+
+impl<T, const N: usize> Deref for Array<T, N> {
+    type Target = [T; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> DerefMut for Array<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for Array<T, N> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> AsMut<[T]> for Array<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T, const N: usize> Borrow<[T]> for Array<T, N> {
+    fn borrow(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> BorrowMut<[T]> for Array<T, N> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Array<T, N> {
+    fn from(values: [T; N]) -> Self {
+        Array::new(values)
+    }
+}
+
+impl<T, const N: usize> From<Array<T, N>> for [T; N] {
+    fn from(array: Array<T, N>) -> Self {
+        array.0
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Array<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut Array<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+
+    #[repr(C)]
+    struct StructWithArrays {
+        transform: Array<f32, 16>,
+        digest: Array<u8, 32>,
+        tag: i32,
+    }
+
+    #[test]
+    fn unittest_array() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithArrays as "struct_with_arrays*" {
+                static struct_with_arrays x{{{1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1}}, {{0xde, 0xad}}, 5};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.transform[0], 1.0);
+        assert_eq!(x.transform.iter().sum::<f32>(), 4.0);
+        assert_eq!(&x.digest[..3], &[0xde, 0xad, 0]);
+        assert_eq!(x.tag, 5);
+
+        x.transform[12] = 3.0;
+        x.digest = [0xff; 32].into();
+        x.digest[31] = 0;
+
+        let x: &StructWithArrays = x;
+        let (size, offset, sum) = unsafe {
+            let mut offset = 0usize;
+            let mut sum = 0usize;
+            let size = cpp!([x as "const struct_with_arrays*", mut offset as "size_t", mut sum as "size_t"]
+                  -> usize as "size_t" {
+                offset = offsetof(struct_with_arrays, tag);
+                for (uint8_t byte : x->digest) {
+                    sum += byte;
+                }
+                sum += x->transform[12];
+                return sizeof(struct_with_arrays);
+            });
+            (size, offset, sum)
+        };
+        assert_eq!(size, size_of::<StructWithArrays>());
+        assert_eq!(offset, 16 * 4 + 32);
+        assert_eq!(sum, 0xff * 31 + 3);
+
+        let digest: [u8; 32] = x.digest.into();
+        assert_eq!(Array::new(digest), x.digest);
+    }
+}
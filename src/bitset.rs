@@ -0,0 +1,488 @@
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use std::str::FromStr;
+
+cpp! {{
+    #include <bitset>
+    #include <string>
+
+    #if defined(CPP_STL_LIBCXX)
+    typedef size_t bitset_word;
+    #else
+    typedef unsigned long bitset_word;
+    #endif
+
+    template <size_t N>
+    struct bitset_words {
+        static const size_t bits = 8 * sizeof(bitset_word);
+        static const bool value = sizeof(std::bitset<N>) == (N + bits - 1) / bits * sizeof(bitset_word)
+            && alignof(std::bitset<N>) == alignof(bitset_word);
+    };
+
+    static_assert(bitset_words<1>::value, "");
+    static_assert(bitset_words<64>::value, "");
+    static_assert(bitset_words<100>::value, "");
+    static_assert(bitset_words<256>::value, "");
+
+    struct struct_with_bitsets {
+        std::bitset<64> flags;
+        std::bitset<256> mask;
+        std::bitset<100> odd;
+    };
+}}
+
+/// The word type libstdc++ stores bits in, `unsigned long`.
+#[cfg(not(cpp_stl_libcxx))]
+pub type Word = libc::c_ulong;
+
+/// The word type libc++ stores bits in, `size_t`.
+#[cfg(cpp_stl_libcxx)]
+pub type Word = libc::size_t;
+
+const WORD_BITS: usize = Word::BITS as usize;
+
+/// Marker naming the number of bits of a `std::bitset<N>`.
+pub struct Bits<const N: usize>;
+
+/// Sizes of `std::bitset<N>` for which the word storage is known, i.e.
+/// `[Word; N.div_ceil(WORD_BITS)]`.
+///
+/// It is implemented for 1 to 256 bits and for 512, 1024, 2048 and 4096 bits, as the
+/// array length cannot depend on `N` yet.
+pub trait BitsetWords {
+    type Words: Copy + Eq + Hash + AsRef<[Word]> + AsMut<[Word]>;
+
+    const ZEROS: Self::Words;
+}
+
+macro_rules! bitset_words {
+    ($($n:literal)*) => ($(
+        impl BitsetWords for Bits<$n> {
+            type Words = [Word; usize::div_ceil($n, WORD_BITS)];
+
+            const ZEROS: Self::Words = [0; usize::div_ceil($n, WORD_BITS)];
+        }
+    )*)
+}
+
+bitset_words! {
+    1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+    33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63 64
+    65 66 67 68 69 70 71 72 73 74 75 76 77 78 79 80 81 82 83 84 85 86 87 88 89 90 91 92 93 94 95 96
+    97 98 99 100 101 102 103 104 105 106 107 108 109 110 111 112 113 114 115 116 117 118 119 120
+    121 122 123 124 125 126 127 128 129 130 131 132 133 134 135 136 137 138 139 140 141 142 143 144
+    145 146 147 148 149 150 151 152 153 154 155 156 157 158 159 160 161 162 163 164 165 166 167 168
+    169 170 171 172 173 174 175 176 177 178 179 180 181 182 183 184 185 186 187 188 189 190 191 192
+    193 194 195 196 197 198 199 200 201 202 203 204 205 206 207 208 209 210 211 212 213 214 215 216
+    217 218 219 220 221 222 223 224 225 226 227 228 229 230 231 232 233 234 235 236 237 238 239 240
+    241 242 243 244 245 246 247 248 249 250 251 252 253 254 255 256
+    512 1024 2048 4096
+}
+
+/// `std::bitset<N>`, which is laid out as an array of words with bit `i` stored in
+/// word `i / WORD_BITS` at position `i % WORD_BITS`, where `WORD_BITS` is the width of
+/// `Word`.
+///
+/// The bits beyond `N` in the last word are always zero, as in C++.
+#[repr(C)]
+pub struct Bitset<const N: usize>
+where
+    Bits<N>: BitsetWords,
+{
+    words: <Bits<N> as BitsetWords>::Words,
+}
+
+impl<const N: usize> Bitset<N>
+where
+    Bits<N>: BitsetWords,
+{
+    /// Creates a bitset with all bits unset.
+    pub fn new() -> Self {
+        Bitset {
+            words: <Bits<N> as BitsetWords>::ZEROS,
+        }
+    }
+
+    /// Creates a bitset from the lowest `N` bits of `value`, as `std::bitset(unsigned long long)`.
+    pub fn from_ullong(value: u64) -> Self {
+        let mut bitset = Self::new();
+        for (i, word) in bitset.words.as_mut().iter_mut().enumerate() {
+            let shift = i * WORD_BITS;
+            if shift < 64 {
+                *word = (value >> shift) as Word;
+            }
+        }
+        bitset.sanitize();
+        bitset
+    }
+
+    /// Returns the bits as an integer, or `None` if a bit beyond the 64th is set where
+    /// `to_ullong()` throws `std::overflow_error`.
+    pub fn to_ullong(&self) -> Option<u64> {
+        let mut value = 0;
+        for (i, &word) in self.as_words().iter().enumerate() {
+            let shift = i * WORD_BITS;
+            if shift < 64 {
+                // `Word` is narrower than `u64` on LLP64 and 32-bit targets.
+                #[allow(clippy::useless_conversion)]
+                let word = u64::from(word);
+                value |= word << shift;
+            } else if word != 0 {
+                return None;
+            }
+        }
+        Some(value)
+    }
+
+    pub fn as_words(&self) -> &[Word] {
+        self.words.as_ref()
+    }
+
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `pos >= N`.
+    pub fn test(&self, pos: usize) -> bool {
+        let (index, mask) = Self::locate(pos);
+        self.words.as_ref()[index] & mask != 0
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `pos >= N`.
+    pub fn set(&mut self, pos: usize, value: bool) {
+        let (index, mask) = Self::locate(pos);
+        let word = &mut self.words.as_mut()[index];
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    pub fn set_all(&mut self) {
+        for word in self.words.as_mut() {
+            *word = !0;
+        }
+        self.sanitize();
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `pos >= N`.
+    pub fn reset(&mut self, pos: usize) {
+        self.set(pos, false);
+    }
+
+    pub fn reset_all(&mut self) {
+        self.words = <Bits<N> as BitsetWords>::ZEROS;
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `pos >= N`.
+    pub fn flip(&mut self, pos: usize) {
+        let (index, mask) = Self::locate(pos);
+        self.words.as_mut()[index] ^= mask;
+    }
+
+    pub fn flip_all(&mut self) {
+        for word in self.words.as_mut() {
+            *word = !*word;
+        }
+        self.sanitize();
+    }
+
+    /// Returns the number of set bits.
+    pub fn count(&self) -> usize {
+        self.as_words()
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn any(&self) -> bool {
+        self.as_words().iter().any(|&word| word != 0)
+    }
+
+    pub fn all(&self) -> bool {
+        self.count() == N
+    }
+
+    pub fn none(&self) -> bool {
+        !self.any()
+    }
+
+    fn locate(pos: usize) -> (usize, Word) {
+        assert!(
+            pos < N,
+            "bitset position {} out of range for {} bits",
+            pos,
+            N
+        );
+        (pos / WORD_BITS, 1 << (pos % WORD_BITS))
+    }
+
+    // Clears the bits beyond `N` in the last word.
+    fn sanitize(&mut self) {
+        let extra = N % WORD_BITS;
+        if extra != 0 {
+            if let Some(last) = self.words.as_mut().last_mut() {
+                *last &= (1 << extra) - 1;
+            }
+        }
+    }
+}
+
+impl<const N: usize> Clone for Bitset<N>
+where
+    Bits<N>: BitsetWords,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const N: usize> Copy for Bitset<N> where Bits<N>: BitsetWords {}
+
+impl<const N: usize> Default for Bitset<N>
+where
+    Bits<N>: BitsetWords,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> PartialEq for Bitset<N>
+where
+    Bits<N>: BitsetWords,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.words == other.words
+    }
+}
+
+impl<const N: usize> Eq for Bitset<N> where Bits<N>: BitsetWords {}
+
+impl<const N: usize> Hash for Bitset<N>
+where
+    Bits<N>: BitsetWords,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.words.hash(state)
+    }
+}
+
+/// Formats the bits as `to_string()` does, from bit `N - 1` down to bit 0.
+impl<const N: usize> fmt::Display for Bitset<N>
+where
+    Bits<N>: BitsetWords,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for pos in (0..N).rev() {
+            f.write_str(if self.test(pos) { "1" } else { "0" })?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Debug for Bitset<N>
+where
+    Bits<N>: BitsetWords,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bitset<{}>({})", N, self)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBitsetError;
+
+impl fmt::Display for ParseBitsetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid bitset literal")
+    }
+}
+
+impl Error for ParseBitsetError {}
+
+/// Parses a string of `'0'` and `'1'` characters as the `std::bitset(const std::string&)`
+/// constructor does: only the first `N` characters are read, and the last of them is
+/// bit 0.
+impl<const N: usize> FromStr for Bitset<N>
+where
+    Bits<N>: BitsetWords,
+{
+    type Err = ParseBitsetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = &s.as_bytes()[..s.len().min(N)];
+        let mut bitset = Self::new();
+        for (pos, &c) in s.iter().rev().enumerate() {
+            match c {
+                b'0' => {}
+                b'1' => bitset.set(pos, true),
+                _ => return Err(ParseBitsetError),
+            }
+        }
+        Ok(bitset)
+    }
+}
+
+impl<const N: usize> From<u64> for Bitset<N>
+where
+    Bits<N>: BitsetWords,
+{
+    fn from(value: u64) -> Self {
+        Self::from_ullong(value)
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($($op:ident::$f:ident, $op_assign:ident::$f_assign:ident, $sym:tt;)*) => ($(
+        impl<const N: usize> $op for Bitset<N>
+        where
+            Bits<N>: BitsetWords,
+        {
+            type Output = Self;
+
+            fn $f(mut self, rhs: Self) -> Self {
+                self.$f_assign(rhs);
+                self
+            }
+        }
+
+        impl<const N: usize> $op_assign for Bitset<N>
+        where
+            Bits<N>: BitsetWords,
+        {
+            fn $f_assign(&mut self, rhs: Self) {
+                for (word, rhs) in self.words.as_mut().iter_mut().zip(rhs.as_words()) {
+                    *word $sym *rhs;
+                }
+            }
+        }
+    )*)
+}
+
+impl_bit_op! {
+    BitAnd::bitand, BitAndAssign::bitand_assign, &=;
+    BitOr::bitor, BitOrAssign::bitor_assign, |=;
+    BitXor::bitxor, BitXorAssign::bitxor_assign, ^=;
+}
+
+impl<const N: usize> Not for Bitset<N>
+where
+    Bits<N>: BitsetWords,
+{
+    type Output = Self;
+
+    fn not(mut self) -> Self {
+        self.flip_all();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libc::size_t;
+
+    #[repr(C)]
+    struct StructWithBitsets {
+        flags: Bitset<64>,
+        mask: Bitset<256>,
+        odd: Bitset<100>,
+    }
+
+    #[test]
+    fn unittest_bitset() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithBitsets as "struct_with_bitsets*" {
+                static struct_with_bitsets x{
+                    0b1010, std::bitset<256>().set(200).set(3), std::bitset<100>().set(99).set(31)
+                };
+                return &x;
+            })
+        };
+
+        assert_eq!(x.flags.to_ullong(), Some(0b1010));
+        assert!(x.flags.test(1) && !x.flags.test(0));
+        assert_eq!(x.mask.count(), 2);
+        assert!(x.mask.test(200));
+        assert_eq!(x.mask.to_ullong(), None);
+        assert!(x.odd.test(99) && x.odd.test(31) && !x.odd.test(32));
+
+        x.flags.set(63, true);
+        x.flags.flip(1);
+        x.mask.reset(200);
+        x.mask.set(255, true);
+        x.odd.set(32, true);
+
+        let x: &StructWithBitsets = x;
+        let mask = x.mask.to_string();
+        let ptr = mask.as_ptr();
+        let len = mask.len() as size_t;
+        let (flags, count, same) = unsafe {
+            let mut count = 0usize;
+            let mut same = false;
+            let flags = cpp!([x as "const struct_with_bitsets*", ptr as "const char*", len as "size_t",
+                              mut count as "size_t", mut same as "bool"] -> u64 as "uint64_t" {
+                std::string mask(ptr, len);
+                count = x->mask.count() + x->odd.count();
+                same = x->mask.to_string() == mask && std::bitset<256>(mask) == x->mask;
+                return x->flags.to_ullong();
+            });
+            (flags, count, same)
+        };
+        assert_eq!(flags, 1 << 63 | 0b1000);
+        assert_eq!(count, 5);
+        assert!(same);
+    }
+
+    #[test]
+    fn unittest_bitset_ops() {
+        let a = Bitset::<64>::from(0b1100);
+        let b: Bitset<64> = "1010".parse().unwrap();
+        assert_eq!((a & b).to_ullong(), Some(0b1000));
+        assert_eq!((a | b).to_ullong(), Some(0b1110));
+        assert_eq!((a ^ b).to_ullong(), Some(0b0110));
+        assert_eq!((!a).count(), 62);
+        assert!((a | !a).all());
+        assert!((a & !a).none());
+
+        let mut c = Bitset::<256>::new();
+        assert!(c.none());
+        c.set_all();
+        assert!(c.all());
+        assert_eq!(c.count(), 256);
+        c ^= Bitset::from(u64::MAX);
+        assert_eq!(c.count(), 192);
+        assert!(!c.test(63) && c.test(64));
+        c.reset_all();
+        assert_eq!(c, Bitset::default());
+
+        assert_eq!(a.to_string().len(), 64);
+        assert_eq!(a.to_string().parse::<Bitset<64>>(), Ok(a));
+        assert_eq!("102".parse::<Bitset<64>>(), Err(ParseBitsetError));
+        assert_eq!(
+            format!("{}2", "1".repeat(64)).parse(),
+            Ok(!Bitset::<64>::new())
+        );
+        assert_eq!(
+            "1".repeat(40).parse::<Bitset<32>>(),
+            Ok(Bitset::from(u64::from(u32::MAX)))
+        );
+        assert_eq!(Bitset::<100>::from(u64::MAX).to_ullong(), Some(u64::MAX));
+    }
+}
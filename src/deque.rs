@@ -0,0 +1,720 @@
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ops::{Index, IndexMut};
+use std::{ptr, slice};
+
+use libc::size_t;
+
+use crate::bindings::root::rust::*;
+use crate::memory::{UniquePtr, UniquePtrElement};
+
+cpp! {{
+    #include <deque>
+    #include <memory>
+    #include <string>
+
+    #include "wrapper.hpp"
+
+    static_assert(sizeof(std::deque<int32_t>) == sizeof(rust::deque_of_dummy), "");
+    static_assert(sizeof(std::deque<int64_t>) == sizeof(rust::deque_of_dummy), "");
+    static_assert(sizeof(std::deque<void*>) == sizeof(rust::deque_of_dummy), "");
+    static_assert(sizeof(std::deque<dummy_unique_ptr>) == sizeof(rust::deque_of_dummy), "");
+
+    static int32_t deleted_jobs = 0;
+
+    struct job {
+        int32_t id;
+        std::string name;
+
+        job(int32_t i, const char* s): id(i), name(s) {}
+        ~job() { ++deleted_jobs; }
+    };
+
+    struct struct_with_deques {
+        std::deque<int64_t> ticks;
+        std::deque<std::unique_ptr<job>> jobs;
+    };
+}}
+
+/// Element types of `std::deque<T>` for which the instantiation exists in C++.
+pub trait DequeElement: Sized {
+    /// Constructs an empty deque at `deque`.
+    ///
+    /// # Safety
+    ///
+    /// `deque` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(deque: *mut Deque<Self>);
+
+    /// Destroys `deque`.
+    ///
+    /// # Safety
+    ///
+    /// `deque` must not be used after this call.
+    unsafe fn destruct(deque: &mut Deque<Self>);
+
+    fn size(deque: &Deque<Self>) -> usize;
+
+    /// Returns the element at `index`.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than the size of `deque`.
+    unsafe fn get(deque: &Deque<Self>, index: usize) -> *const Self;
+
+    /// Returns the number of elements stored contiguously from `index`.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than the size of `deque`.
+    unsafe fn chunk_len(deque: &Deque<Self>, index: usize) -> usize;
+
+    fn push_front(deque: &mut Deque<Self>, value: Self);
+
+    fn push_back(deque: &mut Deque<Self>, value: Self);
+
+    /// Removes the first element and returns it.
+    ///
+    /// # Safety
+    ///
+    /// `deque` must not be empty.
+    unsafe fn pop_front(deque: &mut Deque<Self>) -> Self;
+
+    /// Removes the last element and returns it.
+    ///
+    /// # Safety
+    ///
+    /// `deque` must not be empty.
+    unsafe fn pop_back(deque: &mut Deque<Self>) -> Self;
+
+    fn clear(deque: &mut Deque<Self>);
+
+    fn swap(deque: &mut Deque<Self>, other: &mut Deque<Self>);
+}
+
+/// `std::deque<T>`.
+///
+/// The elements are stored in fixed-size chunks rather than in one contiguous buffer,
+/// so a deque only derefs to slices through `chunks()`.
+#[repr(C)]
+pub struct Deque<T: DequeElement>(deque_of_dummy, PhantomData<T>);
+
+impl<T: DequeElement> Deque<T> {
+    pub fn new() -> Box<Self> {
+        let deque = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            T::construct(deque);
+            Box::from_raw(deque)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        T::size(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        unsafe { Some(&*T::get(self, index)) }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+        unsafe { Some(&mut *(T::get(self, index) as *mut T)) }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        T::push_front(self, value);
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        T::push_back(self, value);
+    }
+
+    /// Removes the first element and returns it.
+    ///
+    /// A popped `UniquePtr` does not delete its pointee when dropped, so the caller must
+    /// call `UniquePtr::reset` unless it passes the pointer on to C++.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe { Some(T::pop_front(self)) }
+    }
+
+    /// Removes the last element and returns it.
+    ///
+    /// A popped `UniquePtr` does not delete its pointee when dropped, so the caller must
+    /// call `UniquePtr::reset` unless it passes the pointer on to C++.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe { Some(T::pop_back(self)) }
+    }
+
+    pub fn clear(&mut self) {
+        T::clear(self);
+    }
+
+    /// Exchanges the contents with `other` without moving either deque object.
+    pub fn swap(&mut self, other: &mut Self) {
+        T::swap(self, other);
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            deque: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    /// Returns the contiguous runs of elements in order, like `VecDeque::as_slices`.
+    pub fn chunks(&self) -> Chunks<'_, T> {
+        Chunks {
+            deque: self,
+            index: 0,
+        }
+    }
+}
+
+impl<T: DequeElement> Drop for Deque<T> {
+    fn drop(&mut self) {
+        unsafe { T::destruct(self) }
+    }
+}
+
+impl<T: DequeElement> Index<usize> for Deque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T: DequeElement> IndexMut<usize> for Deque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<T> fmt::Debug for Deque<T>
+where
+    T: DequeElement + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: DequeElement> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: DequeElement> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T: DequeElement> FromIterator<T> for Box<Deque<T>> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Deque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T: DequeElement> From<VecDeque<T>> for Box<Deque<T>> {
+    fn from(deque: VecDeque<T>) -> Self {
+        deque.into_iter().collect()
+    }
+}
+
+impl<T: DequeElement + Clone> From<&Deque<T>> for VecDeque<T> {
+    fn from(deque: &Deque<T>) -> Self {
+        deque.iter().cloned().collect()
+    }
+}
+
+pub struct Iter<'a, T: DequeElement> {
+    deque: &'a Deque<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T: DequeElement> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        unsafe { Some(&*T::get(self.deque, self.front - 1)) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: DequeElement> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        unsafe { Some(&*T::get(self.deque, self.back)) }
+    }
+}
+
+impl<'a, T: DequeElement> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct Chunks<'a, T: DequeElement> {
+    deque: &'a Deque<T>,
+    index: usize,
+}
+
+impl<'a, T: DequeElement> Iterator for Chunks<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.deque.len() {
+            return None;
+        }
+
+        unsafe {
+            let ptr = T::get(self.deque, self.index);
+            let len = T::chunk_len(self.deque, self.index);
+            self.index += len;
+            Some(slice::from_raw_parts(ptr, len))
+        }
+    }
+}
+
+impl DequeElement for i32 {
+    unsafe fn construct(deque: *mut Deque<Self>) {
+        cpp!([deque as "std::deque<int32_t>*"] {
+            new (deque) std::deque<int32_t>();
+        })
+    }
+
+    unsafe fn destruct(deque: &mut Deque<Self>) {
+        cpp!([deque as "std::deque<int32_t>*"] {
+            deque->~deque();
+        })
+    }
+
+    fn size(deque: &Deque<Self>) -> usize {
+        unsafe {
+            cpp!([deque as "const std::deque<int32_t>*"] -> size_t as "size_t" {
+                return deque->size();
+            })
+        }
+    }
+
+    unsafe fn get(deque: &Deque<Self>, index: usize) -> *const Self {
+        let index = index as size_t;
+        cpp!([deque as "const std::deque<int32_t>*", index as "size_t"]
+              -> *const i32 as "const int32_t*" {
+            return &(*deque)[index];
+        })
+    }
+
+    unsafe fn chunk_len(deque: &Deque<Self>, index: usize) -> usize {
+        let index = index as size_t;
+        cpp!([deque as "const std::deque<int32_t>*", index as "size_t"] -> size_t as "size_t" {
+            const int32_t* first = &(*deque)[index];
+            size_t len = 1;
+            while (index + len < deque->size() && &(*deque)[index + len] == first + len) {
+                ++len;
+            }
+            return len;
+        })
+    }
+
+    fn push_front(deque: &mut Deque<Self>, value: Self) {
+        unsafe {
+            cpp!([deque as "std::deque<int32_t>*", value as "int32_t"] {
+                deque->push_front(value);
+            })
+        }
+    }
+
+    fn push_back(deque: &mut Deque<Self>, value: Self) {
+        unsafe {
+            cpp!([deque as "std::deque<int32_t>*", value as "int32_t"] {
+                deque->push_back(value);
+            })
+        }
+    }
+
+    unsafe fn pop_front(deque: &mut Deque<Self>) -> Self {
+        cpp!([deque as "std::deque<int32_t>*"] -> i32 as "int32_t" {
+            int32_t value = deque->front();
+            deque->pop_front();
+            return value;
+        })
+    }
+
+    unsafe fn pop_back(deque: &mut Deque<Self>) -> Self {
+        cpp!([deque as "std::deque<int32_t>*"] -> i32 as "int32_t" {
+            int32_t value = deque->back();
+            deque->pop_back();
+            return value;
+        })
+    }
+
+    fn clear(deque: &mut Deque<Self>) {
+        unsafe {
+            cpp!([deque as "std::deque<int32_t>*"] {
+                deque->clear();
+            })
+        }
+    }
+
+    fn swap(deque: &mut Deque<Self>, other: &mut Deque<Self>) {
+        unsafe {
+            cpp!([deque as "std::deque<int32_t>*", other as "std::deque<int32_t>*"] {
+                deque->swap(*other);
+            })
+        }
+    }
+}
+
+impl DequeElement for i64 {
+    unsafe fn construct(deque: *mut Deque<Self>) {
+        cpp!([deque as "std::deque<int64_t>*"] {
+            new (deque) std::deque<int64_t>();
+        })
+    }
+
+    unsafe fn destruct(deque: &mut Deque<Self>) {
+        cpp!([deque as "std::deque<int64_t>*"] {
+            deque->~deque();
+        })
+    }
+
+    fn size(deque: &Deque<Self>) -> usize {
+        unsafe {
+            cpp!([deque as "const std::deque<int64_t>*"] -> size_t as "size_t" {
+                return deque->size();
+            })
+        }
+    }
+
+    unsafe fn get(deque: &Deque<Self>, index: usize) -> *const Self {
+        let index = index as size_t;
+        cpp!([deque as "const std::deque<int64_t>*", index as "size_t"]
+              -> *const i64 as "const int64_t*" {
+            return &(*deque)[index];
+        })
+    }
+
+    unsafe fn chunk_len(deque: &Deque<Self>, index: usize) -> usize {
+        let index = index as size_t;
+        cpp!([deque as "const std::deque<int64_t>*", index as "size_t"] -> size_t as "size_t" {
+            const int64_t* first = &(*deque)[index];
+            size_t len = 1;
+            while (index + len < deque->size() && &(*deque)[index + len] == first + len) {
+                ++len;
+            }
+            return len;
+        })
+    }
+
+    fn push_front(deque: &mut Deque<Self>, value: Self) {
+        unsafe {
+            cpp!([deque as "std::deque<int64_t>*", value as "int64_t"] {
+                deque->push_front(value);
+            })
+        }
+    }
+
+    fn push_back(deque: &mut Deque<Self>, value: Self) {
+        unsafe {
+            cpp!([deque as "std::deque<int64_t>*", value as "int64_t"] {
+                deque->push_back(value);
+            })
+        }
+    }
+
+    unsafe fn pop_front(deque: &mut Deque<Self>) -> Self {
+        cpp!([deque as "std::deque<int64_t>*"] -> i64 as "int64_t" {
+            int64_t value = deque->front();
+            deque->pop_front();
+            return value;
+        })
+    }
+
+    unsafe fn pop_back(deque: &mut Deque<Self>) -> Self {
+        cpp!([deque as "std::deque<int64_t>*"] -> i64 as "int64_t" {
+            int64_t value = deque->back();
+            deque->pop_back();
+            return value;
+        })
+    }
+
+    fn clear(deque: &mut Deque<Self>) {
+        unsafe {
+            cpp!([deque as "std::deque<int64_t>*"] {
+                deque->clear();
+            })
+        }
+    }
+
+    fn swap(deque: &mut Deque<Self>, other: &mut Deque<Self>) {
+        unsafe {
+            cpp!([deque as "std::deque<int64_t>*", other as "std::deque<int64_t>*"] {
+                deque->swap(*other);
+            })
+        }
+    }
+}
+
+/// `std::deque<std::unique_ptr<T>>` is handled as `std::deque<void*>`, so clearing or
+/// destroying the deque from Rust deletes the pointees through `UniquePtrElement` first.
+/// The pointees of popped elements are left to the caller, see `Deque::pop_front`.
+impl<T: UniquePtrElement> DequeElement for UniquePtr<T> {
+    unsafe fn construct(deque: *mut Deque<Self>) {
+        cpp!([deque as "std::deque<void*>*"] {
+            new (deque) std::deque<void*>();
+        })
+    }
+
+    unsafe fn destruct(deque: &mut Deque<Self>) {
+        Self::clear(deque);
+        cpp!([deque as "std::deque<void*>*"] {
+            deque->~deque();
+        })
+    }
+
+    fn size(deque: &Deque<Self>) -> usize {
+        unsafe {
+            cpp!([deque as "const std::deque<void*>*"] -> size_t as "size_t" {
+                return deque->size();
+            })
+        }
+    }
+
+    unsafe fn get(deque: &Deque<Self>, index: usize) -> *const Self {
+        let index = index as size_t;
+        cpp!([deque as "const std::deque<void*>*", index as "size_t"]
+              -> *const c_void as "void* const*" {
+            return &(*deque)[index];
+        }) as *const Self
+    }
+
+    unsafe fn chunk_len(deque: &Deque<Self>, index: usize) -> usize {
+        let index = index as size_t;
+        cpp!([deque as "const std::deque<void*>*", index as "size_t"] -> size_t as "size_t" {
+            void* const* first = &(*deque)[index];
+            size_t len = 1;
+            while (index + len < deque->size() && &(*deque)[index + len] == first + len) {
+                ++len;
+            }
+            return len;
+        })
+    }
+
+    fn push_front(deque: &mut Deque<Self>, value: Self) {
+        let ptr = value.into_raw() as *mut c_void;
+        unsafe {
+            cpp!([deque as "std::deque<void*>*", ptr as "void*"] {
+                deque->push_front(ptr);
+            })
+        }
+    }
+
+    fn push_back(deque: &mut Deque<Self>, value: Self) {
+        let ptr = value.into_raw() as *mut c_void;
+        unsafe {
+            cpp!([deque as "std::deque<void*>*", ptr as "void*"] {
+                deque->push_back(ptr);
+            })
+        }
+    }
+
+    unsafe fn pop_front(deque: &mut Deque<Self>) -> Self {
+        let ptr = cpp!([deque as "std::deque<void*>*"] -> *mut c_void as "void*" {
+            void* ptr = deque->front();
+            deque->pop_front();
+            return ptr;
+        });
+        UniquePtr::from_raw(ptr as *mut T)
+    }
+
+    unsafe fn pop_back(deque: &mut Deque<Self>) -> Self {
+        let ptr = cpp!([deque as "std::deque<void*>*"] -> *mut c_void as "void*" {
+            void* ptr = deque->back();
+            deque->pop_back();
+            return ptr;
+        });
+        UniquePtr::from_raw(ptr as *mut T)
+    }
+
+    fn clear(deque: &mut Deque<Self>) {
+        for index in 0..Self::size(deque) {
+            unsafe { T::delete(ptr::read(Self::get(deque, index)).into_raw()) };
+        }
+        unsafe {
+            cpp!([deque as "std::deque<void*>*"] {
+                deque->clear();
+            })
+        }
+    }
+
+    fn swap(deque: &mut Deque<Self>, other: &mut Deque<Self>) {
+        unsafe {
+            cpp!([deque as "std::deque<void*>*", other as "std::deque<void*>*"] {
+                deque->swap(*other);
+            })
+        }
+    }
+}
+
+#[cfg(all(test, feature = "string"))]
+mod tests {
+    use super::*;
+    use crate::string::String as StlString;
+
+    #[repr(C)]
+    struct Job {
+        id: i32,
+        name: StlString,
+    }
+
+    impl UniquePtrElement for Job {
+        unsafe fn delete(ptr: *mut Self) {
+            cpp!([ptr as "job*"] {
+                delete ptr;
+            })
+        }
+    }
+
+    #[repr(C)]
+    struct StructWithDeques {
+        ticks: Deque<i64>,
+        jobs: Deque<UniquePtr<Job>>,
+    }
+
+    #[test]
+    fn unittest_deque() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithDeques as "struct_with_deques*" {
+                static struct_with_deques x{{2, 3}, {}};
+                x.jobs.emplace_back(new job(1, "build"));
+                x.jobs.emplace_back(new job(2, "test"));
+                return &x;
+            })
+        };
+
+        assert_eq!(x.ticks.len(), 2);
+        x.ticks.push_front(1);
+        x.ticks.push_back(4);
+        assert_eq!(
+            x.ticks.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            x.ticks.iter().rev().copied().collect::<Vec<_>>(),
+            vec![4, 3, 2, 1]
+        );
+        assert_eq!(x.ticks.pop_front(), Some(1));
+        assert_eq!(x.ticks.pop_back(), Some(4));
+        assert_eq!(x.ticks[1], 3);
+        x.ticks[1] = 30;
+        assert_eq!(x.ticks.get(2), None);
+
+        x.ticks.extend(0..1000);
+        assert!(x.ticks.chunks().count() > 1);
+        assert_eq!(
+            x.ticks.chunks().flatten().collect::<Vec<_>>(),
+            x.ticks.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(VecDeque::from(&x.ticks).len(), 1002);
+
+        assert_eq!(x.jobs.len(), 2);
+        assert_eq!(x.jobs[1].name.to_string(), "test");
+        let job = x.jobs.pop_front().unwrap();
+        assert_eq!(job.id, 1);
+        x.jobs.push_back(job);
+        assert_eq!(
+            x.jobs.iter().map(|job| job.id).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+
+        let x: &StructWithDeques = x;
+        let sum = unsafe {
+            cpp!([x as "const struct_with_deques*"] -> i64 as "int64_t" {
+                return x->ticks[1] + x->jobs.back()->id + x->jobs.front()->id;
+            })
+        };
+        assert_eq!(sum, 33);
+    }
+
+    #[test]
+    fn unittest_deque_of_unique_ptr() {
+        let new_job = |id: i32| unsafe {
+            UniquePtr::from_raw(cpp!([id as "int32_t"] -> *mut Job as "job*" {
+                return new job(id, "deploy");
+            }))
+        };
+        let deleted_jobs = || unsafe {
+            cpp!([] -> i32 as "int32_t" {
+                return deleted_jobs;
+            })
+        };
+
+        let mut jobs: Box<Deque<UniquePtr<Job>>> = (0..4).map(new_job).collect();
+        let mut job = jobs.pop_back().unwrap();
+        assert_eq!(job.id, 3);
+        job.reset();
+        assert_eq!(deleted_jobs(), 1);
+
+        jobs.clear();
+        assert_eq!(deleted_jobs(), 4);
+        jobs.push_back(new_job(4));
+        drop(jobs);
+        assert_eq!(deleted_jobs(), 5);
+    }
+
+    #[test]
+    fn unittest_deque_from() {
+        let deque: Box<Deque<i64>> = vec![1, 2, 3].into_iter().collect::<VecDeque<_>>().into();
+        assert_eq!(format!("{:?}", deque), "[1, 2, 3]");
+        assert_eq!(deque.front(), Some(&1));
+        assert_eq!(deque.back(), Some(&3));
+        assert_eq!(deque.chunks().collect::<Vec<_>>(), vec![&[1, 2, 3][..]]);
+    }
+}
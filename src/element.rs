@@ -0,0 +1,33 @@
+use std::borrow::Borrow;
+
+/// A C++ type which can be copied into and out of the containers of this crate.
+///
+/// Elements are passed into C++ as `&Self::Borrowed` and copied out of C++ as
+/// `Self::Owned`, e.g. `&str` and `std::string::String` for `std::string`.
+pub trait Element {
+    type Borrowed: ?Sized;
+    type Owned: Borrow<Self::Borrowed>;
+
+    fn to_rust(&self) -> Self::Owned;
+
+    fn assign(&mut self, value: &Self::Borrowed);
+}
+
+macro_rules! add_impl {
+    ($($t:ty)*) => ($(
+        impl Element for $t {
+            type Borrowed = $t;
+            type Owned = $t;
+
+            fn to_rust(&self) -> Self::Owned {
+                *self
+            }
+
+            fn assign(&mut self, value: &Self::Borrowed) {
+                *self = *value;
+            }
+        }
+    )*)
+}
+
+add_impl!(bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64);
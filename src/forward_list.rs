@@ -0,0 +1,538 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+
+use crate::bindings::root::rust::*;
+use crate::element::Element;
+
+cpp! {{
+    #include <forward_list>
+
+    #include "wrapper.hpp"
+
+    static_assert(sizeof(std::forward_list<int32_t>) == sizeof(rust::forward_list_of_dummy), "");
+    static_assert(
+        sizeof(std::forward_list<int32_t>::iterator) == sizeof(rust::forward_list_iterator_of_dummy), "");
+
+    struct struct_with_forward_list {
+        std::forward_list<int32_t> ids;
+        int32_t checksum;
+    };
+}}
+
+/// Opaque `std::forward_list<T>::iterator`.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct RawIterator(forward_list_iterator_of_dummy);
+
+/// Element types of `std::forward_list<T>` for which the instantiation exists in C++.
+pub trait ForwardListElement: Element + Sized {
+    /// Constructs an empty list at `list`.
+    ///
+    /// # Safety
+    ///
+    /// `list` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(list: *mut ForwardList<Self>);
+
+    /// Destroys `list`.
+    ///
+    /// # Safety
+    ///
+    /// `list` must not be used after this call.
+    unsafe fn destruct(list: &mut ForwardList<Self>);
+
+    fn empty(list: &ForwardList<Self>) -> bool;
+
+    fn clear(list: &mut ForwardList<Self>);
+
+    fn swap(list: &mut ForwardList<Self>, other: &mut ForwardList<Self>);
+
+    fn before_begin(list: &ForwardList<Self>, it: &mut RawIterator);
+
+    fn end(list: &ForwardList<Self>, it: &mut RawIterator);
+
+    fn equal(it: &RawIterator, other: &RawIterator) -> bool;
+
+    /// Returns the element at `it`.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an element of a list.
+    unsafe fn get(it: &RawIterator) -> *mut Self;
+
+    /// Moves `it` to the next element.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an element of a list or before its first element.
+    unsafe fn next(it: &mut RawIterator);
+
+    /// Inserts `value` after `pos`.
+    ///
+    /// # Safety
+    ///
+    /// `pos` must point to an element of `list` or before its first element.
+    unsafe fn insert_after(list: &mut ForwardList<Self>, pos: &RawIterator, value: &Self::Borrowed);
+
+    /// Erases the element after `pos`.
+    ///
+    /// # Safety
+    ///
+    /// `pos` must point before an element of `list`.
+    unsafe fn erase_after(list: &mut ForwardList<Self>, pos: &RawIterator);
+
+    /// Moves all elements of `other` after `pos`.
+    ///
+    /// # Safety
+    ///
+    /// `pos` must point to an element of `list` or before its first element.
+    unsafe fn splice_after(
+        list: &mut ForwardList<Self>,
+        pos: &RawIterator,
+        other: &mut ForwardList<Self>,
+    );
+}
+
+/// `std::forward_list<T>`.
+#[repr(C)]
+pub struct ForwardList<T: ForwardListElement>(forward_list_of_dummy, PhantomData<T>);
+
+impl<T: ForwardListElement> ForwardList<T> {
+    pub fn new() -> Box<Self> {
+        let list = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            T::construct(list);
+            Box::from_raw(list)
+        }
+    }
+
+    /// Counts the elements, which takes linear time as `std::forward_list` has no `size()`.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        T::empty(self)
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.cursor_front_mut().into_current()
+    }
+
+    pub fn push_front(&mut self, value: &T::Borrowed) {
+        self.cursor_front_mut().insert_before(value);
+    }
+
+    pub fn pop_front(&mut self) -> Option<T::Owned> {
+        self.cursor_front_mut().remove_current()
+    }
+
+    pub fn clear(&mut self) {
+        T::clear(self);
+    }
+
+    /// Exchanges the contents with `other` without moving either list object.
+    pub fn swap(&mut self, other: &mut Self) {
+        T::swap(self, other);
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut front = RawIterator::default();
+        let mut end = RawIterator::default();
+        T::before_begin(self, &mut front);
+        unsafe { T::next(&mut front) };
+        T::end(self, &mut end);
+
+        Iter {
+            front,
+            end,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a cursor at the first element, or at the "ghost" position past the last
+    /// element if the list is empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        let mut prev = RawIterator::default();
+        T::before_begin(self, &mut prev);
+        CursorMut { list: self, prev }
+    }
+}
+
+impl<T: ForwardListElement> Drop for ForwardList<T> {
+    fn drop(&mut self) {
+        unsafe { T::destruct(self) }
+    }
+}
+
+impl<T> fmt::Debug for ForwardList<T>
+where
+    T: ForwardListElement + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: ForwardListElement> IntoIterator for &'a ForwardList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Appends the values at the back, which first walks to the end of the list.
+impl<T, Q> Extend<Q> for ForwardList<T>
+where
+    T: ForwardListElement,
+    Q: Borrow<T::Borrowed>,
+{
+    fn extend<I: IntoIterator<Item = Q>>(&mut self, iter: I) {
+        let mut cursor = self.cursor_front_mut();
+        while cursor.current().is_some() {
+            cursor.move_next();
+        }
+        for value in iter {
+            cursor.insert_before(value.borrow());
+        }
+    }
+}
+
+impl<T, Q> FromIterator<Q> for Box<ForwardList<T>>
+where
+    T: ForwardListElement,
+    Q: Borrow<T::Borrowed>,
+{
+    fn from_iter<I: IntoIterator<Item = Q>>(iter: I) -> Self {
+        let mut list = ForwardList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: ForwardListElement> From<&ForwardList<T>> for Vec<T::Owned> {
+    fn from(list: &ForwardList<T>) -> Self {
+        list.iter().map(T::to_rust).collect()
+    }
+}
+
+pub struct Iter<'a, T: ForwardListElement> {
+    front: RawIterator,
+    end: RawIterator,
+    _marker: PhantomData<&'a ForwardList<T>>,
+}
+
+impl<'a, T: ForwardListElement> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if T::equal(&self.front, &self.end) {
+            return None;
+        }
+
+        unsafe {
+            let value = &*T::get(&self.front);
+            T::next(&mut self.front);
+            Some(value)
+        }
+    }
+}
+
+/// A cursor over a `ForwardList` which can edit it in place.
+///
+/// The cursor holds the iterator before the current element, which the `*_after`
+/// operations of `std::forward_list` need. Past the last element it points to a "ghost"
+/// position, from which `move_next` wraps around to the first element.
+pub struct CursorMut<'a, T: ForwardListElement> {
+    list: &'a mut ForwardList<T>,
+    prev: RawIterator,
+}
+
+impl<'a, T: ForwardListElement> CursorMut<'a, T> {
+    fn position(&self) -> RawIterator {
+        let mut it = self.prev;
+        unsafe { T::next(&mut it) };
+        it
+    }
+
+    fn is_ghost(&self) -> bool {
+        let mut end = RawIterator::default();
+        T::end(self.list, &mut end);
+        T::equal(&self.position(), &end)
+    }
+
+    fn is_before_begin(&self) -> bool {
+        let mut before_begin = RawIterator::default();
+        T::before_begin(self.list, &mut before_begin);
+        T::equal(&self.prev, &before_begin)
+    }
+
+    fn into_current(self) -> Option<&'a mut T> {
+        if self.is_ghost() {
+            return None;
+        }
+        unsafe { Some(&mut *T::get(&self.position())) }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        if self.is_ghost() {
+            return None;
+        }
+        unsafe { Some(&mut *T::get(&self.position())) }
+    }
+
+    pub fn move_next(&mut self) {
+        if self.is_ghost() {
+            T::before_begin(self.list, &mut self.prev);
+        } else {
+            unsafe { T::next(&mut self.prev) };
+        }
+    }
+
+    /// Inserts `value` after the current element, or at the front at the ghost position.
+    pub fn insert_after(&mut self, value: &T::Borrowed) {
+        if self.is_ghost() {
+            let was_empty = self.is_before_begin();
+            let mut before_begin = RawIterator::default();
+            T::before_begin(self.list, &mut before_begin);
+            unsafe { T::insert_after(self.list, &before_begin, value) };
+            if was_empty {
+                unsafe { T::next(&mut self.prev) };
+            }
+        } else {
+            let pos = self.position();
+            unsafe { T::insert_after(self.list, &pos, value) };
+        }
+    }
+
+    /// Inserts `value` before the current element, or at the back at the ghost position.
+    pub fn insert_before(&mut self, value: &T::Borrowed) {
+        unsafe {
+            T::insert_after(self.list, &self.prev, value);
+            T::next(&mut self.prev);
+        }
+    }
+
+    /// Removes the current element and moves the cursor to the next one.
+    pub fn remove_current(&mut self) -> Option<T::Owned> {
+        if self.is_ghost() {
+            return None;
+        }
+
+        unsafe {
+            let value = (*T::get(&self.position())).to_rust();
+            T::erase_after(self.list, &self.prev);
+            Some(value)
+        }
+    }
+
+    /// Moves all elements of `other` after the current element, or at the front at the
+    /// ghost position.
+    ///
+    /// This takes linear time in the length of `other`, like
+    /// `std::forward_list::splice_after`.
+    pub fn splice_after(&mut self, other: &mut ForwardList<T>) {
+        if self.is_ghost() {
+            let was_empty = self.is_before_begin();
+            let mut before_begin = RawIterator::default();
+            T::before_begin(self.list, &mut before_begin);
+            unsafe { T::splice_after(self.list, &before_begin, other) };
+            while was_empty && !self.is_ghost() {
+                unsafe { T::next(&mut self.prev) };
+            }
+        } else {
+            let pos = self.position();
+            unsafe { T::splice_after(self.list, &pos, other) };
+        }
+    }
+}
+
+impl ForwardListElement for i32 {
+    unsafe fn construct(list: *mut ForwardList<Self>) {
+        cpp!([list as "std::forward_list<int32_t>*"] {
+            new (list) std::forward_list<int32_t>();
+        })
+    }
+
+    unsafe fn destruct(list: &mut ForwardList<Self>) {
+        cpp!([list as "std::forward_list<int32_t>*"] {
+            list->~forward_list();
+        })
+    }
+
+    fn empty(list: &ForwardList<Self>) -> bool {
+        unsafe {
+            cpp!([list as "const std::forward_list<int32_t>*"] -> bool as "bool" {
+                return list->empty();
+            })
+        }
+    }
+
+    fn clear(list: &mut ForwardList<Self>) {
+        unsafe {
+            cpp!([list as "std::forward_list<int32_t>*"] {
+                list->clear();
+            })
+        }
+    }
+
+    fn swap(list: &mut ForwardList<Self>, other: &mut ForwardList<Self>) {
+        unsafe {
+            cpp!([list as "std::forward_list<int32_t>*", other as "std::forward_list<int32_t>*"] {
+                list->swap(*other);
+            })
+        }
+    }
+
+    fn before_begin(list: &ForwardList<Self>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([list as "const std::forward_list<int32_t>*",
+                  it as "std::forward_list<int32_t>::iterator*"] {
+                *it = const_cast<std::forward_list<int32_t>*>(list)->before_begin();
+            })
+        }
+    }
+
+    fn end(list: &ForwardList<Self>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([list as "const std::forward_list<int32_t>*",
+                  it as "std::forward_list<int32_t>::iterator*"] {
+                *it = const_cast<std::forward_list<int32_t>*>(list)->end();
+            })
+        }
+    }
+
+    fn equal(it: &RawIterator, other: &RawIterator) -> bool {
+        unsafe {
+            cpp!([it as "const std::forward_list<int32_t>::iterator*",
+                  other as "const std::forward_list<int32_t>::iterator*"] -> bool as "bool" {
+                return *it == *other;
+            })
+        }
+    }
+
+    unsafe fn get(it: &RawIterator) -> *mut Self {
+        cpp!([it as "const std::forward_list<int32_t>::iterator*"] -> *mut i32 as "int32_t*" {
+            return &**it;
+        })
+    }
+
+    unsafe fn next(it: &mut RawIterator) {
+        cpp!([it as "std::forward_list<int32_t>::iterator*"] {
+            ++*it;
+        })
+    }
+
+    unsafe fn insert_after(list: &mut ForwardList<Self>, pos: &RawIterator, value: &i32) {
+        let value = *value;
+        cpp!([list as "std::forward_list<int32_t>*",
+              pos as "const std::forward_list<int32_t>::iterator*", value as "int32_t"] {
+            list->insert_after(*pos, value);
+        })
+    }
+
+    unsafe fn erase_after(list: &mut ForwardList<Self>, pos: &RawIterator) {
+        cpp!([list as "std::forward_list<int32_t>*",
+              pos as "const std::forward_list<int32_t>::iterator*"] {
+            list->erase_after(*pos);
+        })
+    }
+
+    unsafe fn splice_after(
+        list: &mut ForwardList<Self>,
+        pos: &RawIterator,
+        other: &mut ForwardList<Self>,
+    ) {
+        cpp!([list as "std::forward_list<int32_t>*",
+              pos as "const std::forward_list<int32_t>::iterator*",
+              other as "std::forward_list<int32_t>*"] {
+            list->splice_after(*pos, *other);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    struct StructWithForwardList {
+        ids: ForwardList<i32>,
+        checksum: i32,
+    }
+
+    #[test]
+    fn unittest_forward_list() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithForwardList as "struct_with_forward_list*" {
+                static struct_with_forward_list x{{2, 4}, 0};
+                return &x;
+            })
+        };
+
+        x.ids.push_front(&1);
+        assert_eq!(x.ids.iter().copied().collect::<Vec<_>>(), vec![1, 2, 4]);
+        assert_eq!(x.ids.len(), 3);
+
+        let mut cursor = x.ids.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_after(&3);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(cursor.current(), None);
+        cursor.insert_before(&5);
+        cursor.insert_after(&0);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 0));
+        *cursor.current().unwrap() = -1;
+
+        let mut other: Box<ForwardList<i32>> = vec![7, 8].into_iter().collect();
+        cursor.move_next();
+        cursor.splice_after(&mut other);
+        assert!(other.is_empty());
+
+        assert_eq!(Vec::from(&x.ids), vec![-1, 1, 7, 8, 2, 3, 5]);
+
+        x.checksum = unsafe {
+            let x: &StructWithForwardList = x;
+            cpp!([x as "const struct_with_forward_list*"] -> i32 as "int32_t" {
+                int32_t sum = 0;
+                for (int32_t id : x->ids) {
+                    sum = sum * 10 + id;
+                }
+                return sum;
+            })
+        };
+        assert_eq!(x.checksum, -821_765);
+    }
+
+    #[test]
+    fn unittest_forward_list_empty() {
+        let mut list = ForwardList::<i32>::new();
+        assert!(list.is_empty());
+        assert_eq!(list.pop_front(), None);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(&2);
+        assert_eq!(cursor.current(), None);
+        cursor.insert_after(&1);
+        let mut front: Box<ForwardList<i32>> = vec![0].into_iter().collect();
+        cursor.splice_after(&mut front);
+        cursor.insert_before(&3);
+
+        list.extend(vec![4, 5]);
+        assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, 4, 5]");
+        assert_eq!(list.pop_front(), Some(0));
+        assert_eq!(list.front(), Some(&1));
+    }
+}
@@ -0,0 +1,370 @@
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+
+use libc::size_t;
+
+use crate::bindings::root::rust::*;
+use crate::string::String as StlString;
+
+cpp! {{
+    #include <cstdint>
+    #include <functional>
+    #include <memory>
+    #include <string>
+
+    #include "wrapper.hpp"
+
+    typedef std::function<void(int32_t, const std::string&)> function_of_void_int32_t_string;
+    typedef std::function<int64_t(int64_t)> function_of_int64_t_int64_t;
+
+    static_assert(sizeof(function_of_void_int32_t_string) == sizeof(rust::function_of_dummy), "");
+    static_assert(sizeof(function_of_int64_t_int64_t) == sizeof(rust::function_of_dummy), "");
+
+    // A boxed `dyn FnMut`, i.e. a pair of data and vtable pointers.
+    struct rust_closure {
+        void* data;
+        void* vtable;
+    };
+
+    struct event_source {
+        function_of_void_int32_t_string on_event;
+        function_of_int64_t_int64_t transform;
+    };
+}}
+
+/// Signatures of `std::function<R(Args...)>` for which the instantiation exists in C++.
+///
+/// A signature is named by the corresponding Rust function pointer type, e.g.
+/// `fn(i32, &StlString)` for `std::function<void(int32_t, const std::string&)>`.
+pub trait Signature: Sized {
+    /// The Rust closure type a `std::function` of this signature can hold.
+    type Closure: ?Sized;
+
+    /// Constructs an empty function at `function`.
+    ///
+    /// # Safety
+    ///
+    /// `function` must point to uninitialized memory large enough for `Function<Self>`.
+    unsafe fn construct(function: *mut Function<Self>);
+
+    /// # Safety
+    ///
+    /// `function` must not be used after this call.
+    unsafe fn destruct(function: &mut Function<Self>);
+
+    /// Replaces the target of `function` with `closure`, which is dropped when the last
+    /// copy of the `std::function` is destroyed in C++.
+    fn assign(function: &mut Function<Self>, closure: Box<Self::Closure>);
+
+    fn reset(function: &mut Function<Self>);
+
+    fn is_empty(function: &Function<Self>) -> bool;
+
+    fn swap(function: &mut Function<Self>, other: &mut Function<Self>);
+}
+
+/// `std::function`, which type-erases any copyable C++ callable of the signature `S`.
+///
+/// A Rust closure is shared by every copy of the `std::function` made in C++, as
+/// `std::function` requires its target to be copyable. The closure must not call the
+/// function it is stored in.
+///
+/// It is neither `Send` nor `Sync`, as neither the closures nor the C++ callables it holds
+/// need to be.
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<cpp_stl::functional::Function<fn(i64) -> i64>>();
+/// ```
+#[repr(C)]
+pub struct Function<S: Signature>(function_of_dummy, PhantomData<(S, *const ())>);
+
+impl<S: Signature> Function<S> {
+    /// Creates an empty function.
+    pub fn new() -> Box<Self> {
+        let function = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            S::construct(function);
+            Box::from_raw(function)
+        }
+    }
+
+    /// Creates a function calling `closure`.
+    pub fn from_closure(closure: Box<S::Closure>) -> Box<Self> {
+        let mut function = Self::new();
+        function.set(closure);
+        function
+    }
+
+    pub fn set(&mut self, closure: Box<S::Closure>) {
+        S::assign(self, closure)
+    }
+
+    pub fn clear(&mut self) {
+        S::reset(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        S::is_empty(self)
+    }
+
+    pub fn swap(&mut self, other: &mut Self) {
+        S::swap(self, other)
+    }
+}
+
+impl<S: Signature> Drop for Function<S> {
+    fn drop(&mut self) {
+        unsafe { S::destruct(self) }
+    }
+}
+
+impl Signature for fn(i32, &StlString) {
+    type Closure = dyn FnMut(i32, &StlString);
+
+    unsafe fn construct(function: *mut Function<Self>) {
+        cpp!([function as "function_of_void_int32_t_string*"] {
+            new (function) function_of_void_int32_t_string();
+        })
+    }
+
+    unsafe fn destruct(function: &mut Function<Self>) {
+        cpp!([function as "function_of_void_int32_t_string*"] {
+            function->~function_of_void_int32_t_string();
+        })
+    }
+
+    fn assign(function: &mut Function<Self>, closure: Box<Self::Closure>) {
+        let closure = Box::into_raw(closure);
+        unsafe {
+            cpp!([function as "function_of_void_int32_t_string*", closure as "rust_closure"] {
+                std::shared_ptr<rust_closure> target(new rust_closure(closure), [](rust_closure* owned) {
+                    const rust_closure& closure = *owned;
+                    rust!(cpp_stl_drop_function_of_void_int32_t_string [
+                        closure: *mut dyn FnMut(i32, &StlString) as "rust_closure"
+                    ] {
+                        drop(unsafe { Box::from_raw(closure) });
+                    });
+                    delete owned;
+                });
+                *function = [target](int32_t id, const std::string& name) {
+                    const rust_closure& closure = *target;
+                    rust!(cpp_stl_call_function_of_void_int32_t_string [
+                        closure: *mut dyn FnMut(i32, &StlString) as "rust_closure",
+                        id: i32 as "int32_t",
+                        name: &StlString as "const std::string&"
+                    ] {
+                        unsafe { (*closure)(id, name) }
+                    });
+                };
+            })
+        }
+    }
+
+    fn reset(function: &mut Function<Self>) {
+        unsafe {
+            cpp!([function as "function_of_void_int32_t_string*"] {
+                *function = nullptr;
+            })
+        }
+    }
+
+    fn is_empty(function: &Function<Self>) -> bool {
+        unsafe {
+            cpp!([function as "const function_of_void_int32_t_string*"] -> bool as "bool" {
+                return !*function;
+            })
+        }
+    }
+
+    fn swap(function: &mut Function<Self>, other: &mut Function<Self>) {
+        unsafe {
+            cpp!([function as "function_of_void_int32_t_string*", other as "function_of_void_int32_t_string*"] {
+                function->swap(*other);
+            })
+        }
+    }
+}
+
+impl Function<fn(i32, &StlString)> {
+    /// Calls the target.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function is empty.
+    pub fn call(&mut self, id: i32, name: &str) {
+        assert!(!self.is_empty(), "call of an empty std::function");
+        let function = self;
+        let ptr = name.as_ptr();
+        let len = name.len() as size_t;
+        unsafe {
+            cpp!([function as "function_of_void_int32_t_string*", id as "int32_t", ptr as "const char*", len as "size_t"] {
+                (*function)(id, std::string(ptr, len));
+            })
+        }
+    }
+}
+
+impl Signature for fn(i64) -> i64 {
+    type Closure = dyn FnMut(i64) -> i64;
+
+    unsafe fn construct(function: *mut Function<Self>) {
+        cpp!([function as "function_of_int64_t_int64_t*"] {
+            new (function) function_of_int64_t_int64_t();
+        })
+    }
+
+    unsafe fn destruct(function: &mut Function<Self>) {
+        cpp!([function as "function_of_int64_t_int64_t*"] {
+            function->~function_of_int64_t_int64_t();
+        })
+    }
+
+    fn assign(function: &mut Function<Self>, closure: Box<Self::Closure>) {
+        let closure = Box::into_raw(closure);
+        unsafe {
+            cpp!([function as "function_of_int64_t_int64_t*", closure as "rust_closure"] {
+                std::shared_ptr<rust_closure> target(new rust_closure(closure), [](rust_closure* owned) {
+                    const rust_closure& closure = *owned;
+                    rust!(cpp_stl_drop_function_of_int64_t_int64_t [
+                        closure: *mut dyn FnMut(i64) -> i64 as "rust_closure"
+                    ] {
+                        drop(unsafe { Box::from_raw(closure) });
+                    });
+                    delete owned;
+                });
+                *function = [target](int64_t value) {
+                    const rust_closure& closure = *target;
+                    return rust!(cpp_stl_call_function_of_int64_t_int64_t [
+                        closure: *mut dyn FnMut(i64) -> i64 as "rust_closure",
+                        value: i64 as "int64_t"
+                    ] -> i64 as "int64_t" {
+                        unsafe { (*closure)(value) }
+                    });
+                };
+            })
+        }
+    }
+
+    fn reset(function: &mut Function<Self>) {
+        unsafe {
+            cpp!([function as "function_of_int64_t_int64_t*"] {
+                *function = nullptr;
+            })
+        }
+    }
+
+    fn is_empty(function: &Function<Self>) -> bool {
+        unsafe {
+            cpp!([function as "const function_of_int64_t_int64_t*"] -> bool as "bool" {
+                return !*function;
+            })
+        }
+    }
+
+    fn swap(function: &mut Function<Self>, other: &mut Function<Self>) {
+        unsafe {
+            cpp!([function as "function_of_int64_t_int64_t*", other as "function_of_int64_t_int64_t*"] {
+                function->swap(*other);
+            })
+        }
+    }
+}
+
+impl Function<fn(i64) -> i64> {
+    /// Calls the target.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function is empty.
+    pub fn call(&mut self, value: i64) -> i64 {
+        assert!(!self.is_empty(), "call of an empty std::function");
+        let function = self;
+        unsafe {
+            cpp!([function as "function_of_int64_t_int64_t*", value as "int64_t"] -> i64 as "int64_t" {
+                return (*function)(value);
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[repr(C)]
+    struct EventSource {
+        on_event: Function<fn(i32, &StlString)>,
+        transform: Function<fn(i64) -> i64>,
+    }
+
+    #[test]
+    fn unittest_function() {
+        let x = unsafe {
+            cpp!([] -> &mut EventSource as "event_source*" {
+                static event_source x{nullptr, [](int64_t value) { return value * 2; }};
+                return &x;
+            })
+        };
+
+        assert!(x.on_event.is_empty());
+        assert!(!x.transform.is_empty());
+        assert_eq!(x.transform.call(21), 42);
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let log = events.clone();
+        x.on_event.set(Box::new(move |id, name: &StlString| {
+            log.borrow_mut().push((id, name.to_string()))
+        }));
+        assert_eq!(Rc::strong_count(&events), 2);
+
+        let x: &EventSource = x;
+        unsafe {
+            cpp!([x as "event_source*"] {
+                x->on_event(1, "start");
+                auto copy = x->on_event;
+                x->on_event = nullptr;
+                copy(2, "stop");
+            })
+        };
+        assert!(x.on_event.is_empty());
+        assert_eq!(
+            *events.borrow(),
+            [(1, "start".to_owned()), (2, "stop".to_owned())]
+        );
+        assert_eq!(Rc::strong_count(&events), 1);
+    }
+
+    #[test]
+    fn unittest_function_from_closure() {
+        let mut sum = 0;
+        let mut accumulate = Function::<fn(i64) -> i64>::from_closure(Box::new(move |value| {
+            sum += value;
+            sum
+        }));
+        assert_eq!(accumulate.call(1), 1);
+        assert_eq!(accumulate.call(2), 3);
+
+        let mut negate = Function::<fn(i64) -> i64>::new();
+        negate.set(Box::new(|value: i64| -value));
+        accumulate.swap(&mut negate);
+        assert_eq!(accumulate.call(4), -4);
+        assert_eq!(negate.call(3), 6);
+
+        negate.clear();
+        assert!(negate.is_empty());
+
+        let names = Rc::new(RefCell::new(Vec::new()));
+        let log = names.clone();
+        let mut on_event =
+            Function::<fn(i32, &StlString)>::from_closure(Box::new(move |_, name: &StlString| {
+                log.borrow_mut().push(name.to_string())
+            }));
+        on_event.call(0, "boncheol");
+        assert_eq!(*names.borrow(), ["boncheol"]);
+        drop(on_event);
+        assert_eq!(Rc::strong_count(&names), 1);
+    }
+}
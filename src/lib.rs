@@ -1,25 +1,96 @@
-#![recursion_limit = "128"]
+#![recursion_limit = "256"]
 
 #[macro_use]
 extern crate cpp;
 
+// Lets `#[derive(CppMirror)]` refer to this crate as `::cpp_stl` within it.
+extern crate self as cpp_stl;
+
+#[cfg(feature = "array")]
+pub mod array;
 mod bindings;
+#[cfg(feature = "bitset")]
+pub mod bitset;
+#[cfg(feature = "deque")]
+pub mod deque;
+pub mod element;
+#[cfg(feature = "forward_list")]
+pub mod forward_list;
+#[cfg(feature = "functional")]
+pub mod functional;
+#[cfg(feature = "list")]
+pub mod list;
+#[cfg(feature = "map")]
+pub mod map;
+#[cfg(feature = "vector")]
+pub mod mapping;
+#[cfg(feature = "memory")]
 pub mod memory;
+pub mod mirror;
+#[cfg(cpp_stl_cxx17)]
+#[cfg(feature = "optional")]
+pub mod optional;
+#[cfg(feature = "queue")]
+pub mod queue;
+#[cfg(feature = "set")]
+pub mod set;
+#[cfg(cpp_stl_cxx20)]
+#[cfg(feature = "span")]
+pub mod span;
+#[cfg(feature = "stack")]
+pub mod stack;
+#[cfg(feature = "string")]
 pub mod string;
+#[cfg(feature = "tuple")]
+pub mod tuple;
+#[cfg(feature = "unordered_map")]
+pub mod unordered_map;
+#[cfg(feature = "unordered_set")]
+pub mod unordered_set;
+pub mod utility;
+#[cfg(cpp_stl_cxx17)]
+#[cfg(feature = "variant")]
+pub mod variant;
+#[cfg(feature = "vector")]
 pub mod vector;
 
+cpp! {{
+    // Referenced by "cpp_stl.hpp" to check the ABI of other crates.
+    #if defined(__GLIBCXX__)
+    #if _GLIBCXX_USE_CXX11_ABI
+    extern "C" const char cpp_stl_abi_cxx11 = 0;
+    #else
+    extern "C" const char cpp_stl_abi_old = 0;
+    #endif
+    #endif
+
+    #include "cpp_stl.hpp"
+
+    // Checks the layouts taken from `layouts/`, see `build.rs`.
+    #if defined(CPP_STL_PREBUILT_LAYOUTS)
+    #include "cpp_stl_layouts.hpp"
+    #endif
+}}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
-
+    #[cfg(feature = "string")]
     #[test]
-    fn unittest_layout() {
-        use bindings::root::rust;
+    fn unittest_layout_string() {
+        use super::*;
         use bindings::root::std as stl;
         use std::mem::{align_of, size_of};
 
         assert_eq!(size_of::<string::String>(), size_of::<stl::string>());
         assert_eq!(align_of::<string::String>(), align_of::<stl::string>());
+    }
+
+    #[cfg(feature = "vector")]
+    #[test]
+    fn unittest_layout_vector() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
 
         assert_eq!(
             size_of::<vector::VectorOfI32>(),
@@ -38,5 +109,357 @@ mod tests {
             align_of::<vector::VectorOfUniquePtr<string::String>>(),
             align_of::<rust::vector_of_dummy_unique_ptr>()
         );
+
+        assert_eq!(
+            size_of::<vector::VectorOfPairI32F32>(),
+            size_of::<rust::vector_of_pair_of_int32_t_float>()
+        );
+        assert_eq!(
+            align_of::<vector::VectorOfPairI32F32>(),
+            align_of::<rust::vector_of_pair_of_int32_t_float>()
+        );
+
+        assert_eq!(
+            size_of::<vector::VectorOfString>(),
+            size_of::<rust::vector_of_string>()
+        );
+        assert_eq!(
+            align_of::<vector::VectorOfString>(),
+            align_of::<rust::vector_of_string>()
+        );
+
+        assert_eq!(
+            size_of::<vector::VectorOfVector<vector::VectorOfF32>>(),
+            size_of::<rust::vector_of_dummy_vector>()
+        );
+        assert_eq!(
+            align_of::<vector::VectorOfVector<vector::VectorOfF32>>(),
+            align_of::<rust::vector_of_dummy_vector>()
+        );
+    }
+
+    #[cfg(feature = "memory")]
+    #[test]
+    fn unittest_layout_memory() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<memory::UniquePtr<i32>>(),
+            size_of::<rust::unique_ptr_of_void>()
+        );
+        assert_eq!(
+            size_of::<memory::UniqueArray<f32>>(),
+            size_of::<rust::unique_ptr_of_array>() + size_of::<usize>()
+        );
+        assert_eq!(
+            align_of::<memory::UniqueArray<f32>>(),
+            align_of::<rust::unique_ptr_of_array>()
+        );
+    }
+
+    #[cfg(feature = "map")]
+    #[test]
+    fn unittest_layout_map() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<map::Map<string::String, i32>>(),
+            size_of::<rust::map_of_dummy>()
+        );
+        assert_eq!(
+            align_of::<map::Map<string::String, i32>>(),
+            align_of::<rust::map_of_dummy>()
+        );
+        assert_eq!(
+            size_of::<map::RawIterator>(),
+            size_of::<rust::map_iterator_of_dummy>()
+        );
+
+        assert_eq!(
+            size_of::<map::MultiMap<string::String, i64>>(),
+            size_of::<rust::map_of_dummy>()
+        );
+    }
+
+    #[cfg(feature = "unordered_map")]
+    #[test]
+    fn unittest_layout_unordered_map() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<unordered_map::UnorderedMap<string::String, i64>>(),
+            size_of::<rust::unordered_map_of_dummy>()
+        );
+        assert_eq!(
+            align_of::<unordered_map::UnorderedMap<string::String, i64>>(),
+            align_of::<rust::unordered_map_of_dummy>()
+        );
+        assert_eq!(
+            size_of::<unordered_map::RawIterator>(),
+            size_of::<rust::unordered_map_iterator_of_dummy>()
+        );
+        assert_eq!(
+            size_of::<unordered_map::UnorderedMultiMap<string::String, i64>>(),
+            size_of::<rust::unordered_map_of_dummy>()
+        );
+    }
+
+    #[cfg(feature = "set")]
+    #[test]
+    fn unittest_layout_set() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(size_of::<set::Set<i32>>(), size_of::<rust::set_of_dummy>());
+        assert_eq!(
+            align_of::<set::Set<i32>>(),
+            align_of::<rust::set_of_dummy>()
+        );
+        assert_eq!(
+            size_of::<set::RawIterator>(),
+            size_of::<rust::set_iterator_of_dummy>()
+        );
+    }
+
+    #[cfg(feature = "unordered_set")]
+    #[test]
+    fn unittest_layout_unordered_set() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<unordered_set::UnorderedSet<u64>>(),
+            size_of::<rust::unordered_set_of_dummy>()
+        );
+        assert_eq!(
+            align_of::<unordered_set::UnorderedSet<u64>>(),
+            align_of::<rust::unordered_set_of_dummy>()
+        );
+        assert_eq!(
+            size_of::<unordered_set::RawIterator>(),
+            size_of::<rust::unordered_set_iterator_of_dummy>()
+        );
+    }
+
+    #[cfg(feature = "deque")]
+    #[test]
+    fn unittest_layout_deque() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<deque::Deque<i64>>(),
+            size_of::<rust::deque_of_dummy>()
+        );
+        assert_eq!(
+            align_of::<deque::Deque<i64>>(),
+            align_of::<rust::deque_of_dummy>()
+        );
+    }
+
+    #[cfg(feature = "stack")]
+    #[test]
+    fn unittest_layout_stack() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::size_of;
+
+        assert_eq!(
+            size_of::<stack::Stack<i64>>(),
+            size_of::<rust::deque_of_dummy>()
+        );
+    }
+
+    #[cfg(feature = "queue")]
+    #[test]
+    fn unittest_layout_queue() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<queue::Queue<i32>>(),
+            size_of::<rust::deque_of_dummy>()
+        );
+        assert_eq!(
+            size_of::<queue::PriorityQueue<i64>>(),
+            size_of::<rust::priority_queue_of_dummy>()
+        );
+        assert_eq!(
+            align_of::<queue::PriorityQueue<i64>>(),
+            align_of::<rust::priority_queue_of_dummy>()
+        );
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn unittest_layout_list() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::size_of;
+
+        assert_eq!(
+            size_of::<list::List<string::String>>(),
+            size_of::<rust::list_of_dummy>()
+        );
+        assert_eq!(
+            size_of::<list::RawIterator>(),
+            size_of::<rust::list_iterator_of_dummy>()
+        );
+    }
+
+    #[cfg(feature = "forward_list")]
+    #[test]
+    fn unittest_layout_forward_list() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::size_of;
+
+        assert_eq!(
+            size_of::<forward_list::ForwardList<i32>>(),
+            size_of::<rust::forward_list_of_dummy>()
+        );
+        assert_eq!(
+            size_of::<forward_list::RawIterator>(),
+            size_of::<rust::forward_list_iterator_of_dummy>()
+        );
+    }
+
+    #[cfg(feature = "array")]
+    #[test]
+    fn unittest_layout_array() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<array::Array<f32, 16>>(),
+            size_of::<rust::array_of_float_16>()
+        );
+        assert_eq!(
+            align_of::<array::Array<f32, 16>>(),
+            align_of::<rust::array_of_float_16>()
+        );
+        assert_eq!(
+            size_of::<array::Array<u8, 32>>(),
+            size_of::<rust::array_of_uint8_t_32>()
+        );
+        assert_eq!(
+            align_of::<array::Array<u8, 32>>(),
+            align_of::<rust::array_of_uint8_t_32>()
+        );
+    }
+
+    #[cfg(feature = "bitset")]
+    #[test]
+    fn unittest_layout_bitset() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<bitset::Bitset<64>>(),
+            size_of::<rust::bitset_64>()
+        );
+        assert_eq!(
+            align_of::<bitset::Bitset<64>>(),
+            align_of::<rust::bitset_64>()
+        );
+        assert_eq!(
+            size_of::<bitset::Bitset<256>>(),
+            size_of::<rust::bitset_256>()
+        );
+        assert_eq!(
+            align_of::<bitset::Bitset<256>>(),
+            align_of::<rust::bitset_256>()
+        );
+    }
+
+    #[cfg(feature = "functional")]
+    #[test]
+    fn unittest_layout_functional() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<functional::Function<fn(i64) -> i64>>(),
+            size_of::<rust::function_of_dummy>()
+        );
+        assert_eq!(
+            align_of::<functional::Function<fn(i64) -> i64>>(),
+            align_of::<rust::function_of_dummy>()
+        );
+    }
+
+    #[cfg(cpp_stl_cxx17)]
+    #[cfg(feature = "optional")]
+    #[test]
+    fn unittest_layout_cxx17() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<optional::CppOptional<i64>>(),
+            size_of::<rust::optional_of_int64_t>()
+        );
+        assert_eq!(
+            align_of::<optional::CppOptional<i64>>(),
+            align_of::<rust::optional_of_int64_t>()
+        );
+        assert_eq!(
+            size_of::<optional::CppOptional<string::String>>(),
+            size_of::<rust::optional_of_string>()
+        );
+        assert_eq!(
+            align_of::<optional::CppOptional<string::String>>(),
+            align_of::<rust::optional_of_string>()
+        );
+    }
+
+    #[cfg(cpp_stl_cxx20)]
+    #[cfg(feature = "span")]
+    #[test]
+    fn unittest_layout_cxx20() {
+        use super::*;
+        use bindings::root::rust;
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(
+            size_of::<span::Span<f32>>(),
+            size_of::<rust::span_of_float>()
+        );
+        assert_eq!(
+            align_of::<span::Span<f32>>(),
+            align_of::<rust::span_of_float>()
+        );
+        assert_eq!(
+            size_of::<span::SpanMut<f32>>(),
+            size_of::<rust::span_of_float>()
+        );
+    }
+
+    #[test]
+    fn unittest_abi() {
+        let cxx11 = unsafe {
+            cpp!([] -> bool as "bool" {
+            #if defined(__GLIBCXX__)
+                return _GLIBCXX_USE_CXX11_ABI;
+            #else
+                return true;
+            #endif
+            })
+        };
+        assert_eq!(cxx11, !cfg!(feature = "old_abi"));
     }
 }
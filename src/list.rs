@@ -0,0 +1,611 @@
+use std::borrow::Borrow;
+use std::collections::LinkedList;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::{PhantomData, PhantomPinned};
+use std::mem::MaybeUninit;
+use std::pin::Pin;
+
+use libc::size_t;
+
+use crate::bindings::root::rust::*;
+use crate::element::Element;
+use crate::string::String as StlString;
+
+cpp! {{
+    #include <list>
+    #include <string>
+
+    #include "wrapper.hpp"
+
+    static_assert(sizeof(std::list<std::string>) == sizeof(rust::list_of_dummy), "");
+    static_assert(
+        sizeof(std::list<std::string>::iterator) == sizeof(rust::list_iterator_of_dummy), "");
+
+    struct struct_with_list {
+        int32_t id;
+        std::list<std::string> lines;
+    };
+}}
+
+/// Opaque `std::list<T>::iterator`.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct RawIterator(list_iterator_of_dummy);
+
+/// Element types of `std::list<T>` for which the instantiation exists in C++.
+pub trait ListElement: Element + Sized {
+    /// Constructs an empty list at `list`.
+    ///
+    /// # Safety
+    ///
+    /// `list` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(list: *mut List<Self>);
+
+    /// Destroys `list`.
+    ///
+    /// # Safety
+    ///
+    /// `list` must not be used after this call.
+    unsafe fn destruct(list: &mut List<Self>);
+
+    fn size(list: &List<Self>) -> usize;
+
+    fn clear(list: &mut List<Self>);
+
+    fn swap(list: &mut List<Self>, other: &mut List<Self>);
+
+    fn begin(list: &List<Self>, it: &mut RawIterator);
+
+    fn end(list: &List<Self>, it: &mut RawIterator);
+
+    fn equal(it: &RawIterator, other: &RawIterator) -> bool;
+
+    /// Returns the element at `it`.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an element of a list.
+    unsafe fn get(it: &RawIterator) -> *mut Self;
+
+    /// Moves `it` to the next element.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an element of a list.
+    unsafe fn next(it: &mut RawIterator);
+
+    /// Moves `it` to the previous element.
+    ///
+    /// # Safety
+    ///
+    /// `it` must not point to the first element of a list.
+    unsafe fn prev(it: &mut RawIterator);
+
+    /// Inserts `value` before `pos`.
+    ///
+    /// # Safety
+    ///
+    /// `pos` must be an iterator of `list`.
+    unsafe fn insert(list: &mut List<Self>, pos: &RawIterator, value: &Self::Borrowed);
+
+    /// Erases the element at `it` and moves `it` to the next element.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an element of `list`.
+    unsafe fn erase(list: &mut List<Self>, it: &mut RawIterator);
+
+    /// Moves all elements of `other` before `pos`.
+    ///
+    /// # Safety
+    ///
+    /// `pos` must be an iterator of `list`.
+    unsafe fn splice(list: &mut List<Self>, pos: &RawIterator, other: &mut List<Self>);
+}
+
+/// `std::list<T>`.
+///
+/// libstdc++ keeps pointers into the list object itself, so it is pinned like `Map`.
+#[repr(C)]
+pub struct List<T: ListElement>(list_of_dummy, PhantomData<T>, PhantomPinned);
+
+impl<T: ListElement> List<T> {
+    pub fn new() -> Pin<Box<Self>> {
+        let list = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            T::construct(list);
+            Box::into_pin(Box::from_raw(list))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        T::size(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    pub fn front_mut(self: Pin<&mut Self>) -> Option<&mut T> {
+        self.cursor_front_mut().into_current()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.iter().next_back()
+    }
+
+    pub fn back_mut(self: Pin<&mut Self>) -> Option<&mut T> {
+        self.cursor_back_mut().into_current()
+    }
+
+    pub fn push_front(self: Pin<&mut Self>, value: &T::Borrowed) {
+        self.cursor_front_mut().insert_before(value);
+    }
+
+    pub fn push_back(self: Pin<&mut Self>, value: &T::Borrowed) {
+        self.cursor_ghost_mut().insert_before(value);
+    }
+
+    pub fn pop_front(self: Pin<&mut Self>) -> Option<T::Owned> {
+        self.cursor_front_mut().remove_current()
+    }
+
+    pub fn pop_back(self: Pin<&mut Self>) -> Option<T::Owned> {
+        self.cursor_back_mut().remove_current()
+    }
+
+    pub fn clear(self: Pin<&mut Self>) {
+        T::clear(unsafe { self.get_unchecked_mut() });
+    }
+
+    /// Exchanges the contents with `other` without moving either list object.
+    pub fn swap(self: Pin<&mut Self>, other: Pin<&mut Self>) {
+        unsafe { T::swap(self.get_unchecked_mut(), other.get_unchecked_mut()) };
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut front = RawIterator::default();
+        let mut back = RawIterator::default();
+        T::begin(self, &mut front);
+        T::end(self, &mut back);
+
+        Iter {
+            front,
+            back,
+            len: self.len(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a cursor at the first element, or at the "ghost" position past the last
+    /// element if the list is empty.
+    pub fn cursor_front_mut(self: Pin<&mut Self>) -> CursorMut<'_, T> {
+        let mut current = RawIterator::default();
+        T::begin(&self, &mut current);
+        CursorMut {
+            list: unsafe { self.get_unchecked_mut() },
+            current,
+        }
+    }
+
+    /// Returns a cursor at the last element, or at the "ghost" position if the list is
+    /// empty.
+    pub fn cursor_back_mut(self: Pin<&mut Self>) -> CursorMut<'_, T> {
+        let mut cursor = self.cursor_ghost_mut();
+        cursor.move_prev();
+        cursor
+    }
+
+    fn cursor_ghost_mut(self: Pin<&mut Self>) -> CursorMut<'_, T> {
+        let mut current = RawIterator::default();
+        T::end(&self, &mut current);
+        CursorMut {
+            list: unsafe { self.get_unchecked_mut() },
+            current,
+        }
+    }
+}
+
+impl<T: ListElement> Drop for List<T> {
+    fn drop(&mut self) {
+        unsafe { T::destruct(self) }
+    }
+}
+
+impl<T> fmt::Debug for List<T>
+where
+    T: ListElement + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: ListElement> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, Q> Extend<Q> for Pin<&mut List<T>>
+where
+    T: ListElement,
+    Q: Borrow<T::Borrowed>,
+{
+    fn extend<I: IntoIterator<Item = Q>>(&mut self, iter: I) {
+        let mut cursor = self.as_mut().cursor_ghost_mut();
+        for value in iter {
+            cursor.insert_before(value.borrow());
+        }
+    }
+}
+
+impl<T, Q> FromIterator<Q> for Pin<Box<List<T>>>
+where
+    T: ListElement,
+    Q: Borrow<T::Borrowed>,
+{
+    fn from_iter<I: IntoIterator<Item = Q>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.as_mut().extend(iter);
+        list
+    }
+}
+
+impl<T: ListElement> From<LinkedList<T::Owned>> for Pin<Box<List<T>>> {
+    fn from(list: LinkedList<T::Owned>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T: ListElement> From<&List<T>> for LinkedList<T::Owned> {
+    fn from(list: &List<T>) -> Self {
+        list.iter().map(T::to_rust).collect()
+    }
+}
+
+pub struct Iter<'a, T: ListElement> {
+    front: RawIterator,
+    back: RawIterator,
+    len: usize,
+    _marker: PhantomData<&'a List<T>>,
+}
+
+impl<'a, T: ListElement> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        unsafe {
+            let value = &*T::get(&self.front);
+            T::next(&mut self.front);
+            Some(value)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T: ListElement> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        unsafe {
+            T::prev(&mut self.back);
+            Some(&*T::get(&self.back))
+        }
+    }
+}
+
+impl<'a, T: ListElement> ExactSizeIterator for Iter<'a, T> {}
+
+/// A cursor over a `List` which can edit it in place, like
+/// `std::collections::linked_list::CursorMut`.
+///
+/// Besides the elements, the cursor can point to a "ghost" position, which is
+/// `std::list<T>::end()` and lies between the last and the first element.
+pub struct CursorMut<'a, T: ListElement> {
+    /// Pinned by the list methods that create the cursor.
+    list: &'a mut List<T>,
+    current: RawIterator,
+}
+
+impl<'a, T: ListElement> CursorMut<'a, T> {
+    fn is_ghost(&self) -> bool {
+        let mut end = RawIterator::default();
+        T::end(self.list, &mut end);
+        T::equal(&self.current, &end)
+    }
+
+    fn is_front(&self) -> bool {
+        let mut begin = RawIterator::default();
+        T::begin(self.list, &mut begin);
+        T::equal(&self.current, &begin)
+    }
+
+    /// Returns the iterator after the current position, wrapping around at the ghost.
+    fn following(&self) -> RawIterator {
+        let mut it = self.current;
+        if self.is_ghost() {
+            T::begin(self.list, &mut it);
+        } else {
+            unsafe { T::next(&mut it) };
+        }
+        it
+    }
+
+    fn into_current(self) -> Option<&'a mut T> {
+        if self.is_ghost() {
+            return None;
+        }
+        unsafe { Some(&mut *T::get(&self.current)) }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        if self.is_ghost() {
+            return None;
+        }
+        unsafe { Some(&mut *T::get(&self.current)) }
+    }
+
+    pub fn move_next(&mut self) {
+        self.current = self.following();
+    }
+
+    pub fn move_prev(&mut self) {
+        if self.is_front() {
+            T::end(self.list, &mut self.current);
+        } else {
+            unsafe { T::prev(&mut self.current) };
+        }
+    }
+
+    /// Inserts `value` after the current element, or at the front at the ghost position.
+    pub fn insert_after(&mut self, value: &T::Borrowed) {
+        let pos = self.following();
+        unsafe { T::insert(self.list, &pos, value) };
+    }
+
+    /// Inserts `value` before the current element, or at the back at the ghost position.
+    pub fn insert_before(&mut self, value: &T::Borrowed) {
+        unsafe { T::insert(self.list, &self.current, value) };
+    }
+
+    /// Removes the current element and moves the cursor to the next one.
+    pub fn remove_current(&mut self) -> Option<T::Owned> {
+        if self.is_ghost() {
+            return None;
+        }
+
+        unsafe {
+            let value = (*T::get(&self.current)).to_rust();
+            T::erase(self.list, &mut self.current);
+            Some(value)
+        }
+    }
+
+    /// Moves all elements of `other` after the current element, or at the front at the
+    /// ghost position.
+    pub fn splice_after(&mut self, other: Pin<&mut List<T>>) {
+        let pos = self.following();
+        unsafe { T::splice(self.list, &pos, other.get_unchecked_mut()) };
+    }
+
+    /// Moves all elements of `other` before the current element, or at the back at the
+    /// ghost position.
+    pub fn splice_before(&mut self, other: Pin<&mut List<T>>) {
+        unsafe { T::splice(self.list, &self.current, other.get_unchecked_mut()) };
+    }
+}
+
+impl ListElement for StlString {
+    unsafe fn construct(list: *mut List<Self>) {
+        cpp!([list as "std::list<std::string>*"] {
+            new (list) std::list<std::string>();
+        })
+    }
+
+    unsafe fn destruct(list: &mut List<Self>) {
+        cpp!([list as "std::list<std::string>*"] {
+            list->~list();
+        })
+    }
+
+    fn size(list: &List<Self>) -> usize {
+        unsafe {
+            cpp!([list as "const std::list<std::string>*"] -> size_t as "size_t" {
+                return list->size();
+            })
+        }
+    }
+
+    fn clear(list: &mut List<Self>) {
+        unsafe {
+            cpp!([list as "std::list<std::string>*"] {
+                list->clear();
+            })
+        }
+    }
+
+    fn swap(list: &mut List<Self>, other: &mut List<Self>) {
+        unsafe {
+            cpp!([list as "std::list<std::string>*", other as "std::list<std::string>*"] {
+                list->swap(*other);
+            })
+        }
+    }
+
+    fn begin(list: &List<Self>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([list as "const std::list<std::string>*", it as "std::list<std::string>::iterator*"] {
+                *it = const_cast<std::list<std::string>*>(list)->begin();
+            })
+        }
+    }
+
+    fn end(list: &List<Self>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([list as "const std::list<std::string>*", it as "std::list<std::string>::iterator*"] {
+                *it = const_cast<std::list<std::string>*>(list)->end();
+            })
+        }
+    }
+
+    fn equal(it: &RawIterator, other: &RawIterator) -> bool {
+        unsafe {
+            cpp!([it as "const std::list<std::string>::iterator*",
+                  other as "const std::list<std::string>::iterator*"] -> bool as "bool" {
+                return *it == *other;
+            })
+        }
+    }
+
+    unsafe fn get(it: &RawIterator) -> *mut Self {
+        cpp!([it as "const std::list<std::string>::iterator*"] -> *mut StlString as "std::string*" {
+            return &**it;
+        })
+    }
+
+    unsafe fn next(it: &mut RawIterator) {
+        cpp!([it as "std::list<std::string>::iterator*"] {
+            ++*it;
+        })
+    }
+
+    unsafe fn prev(it: &mut RawIterator) {
+        cpp!([it as "std::list<std::string>::iterator*"] {
+            --*it;
+        })
+    }
+
+    unsafe fn insert(list: &mut List<Self>, pos: &RawIterator, value: &str) {
+        let ptr = value.as_ptr();
+        let len = value.len() as size_t;
+        cpp!([list as "std::list<std::string>*", pos as "const std::list<std::string>::iterator*",
+              ptr as "const char*", len as "size_t"] {
+            list->emplace(*pos, ptr, len);
+        })
+    }
+
+    unsafe fn erase(list: &mut List<Self>, it: &mut RawIterator) {
+        cpp!([list as "std::list<std::string>*", it as "std::list<std::string>::iterator*"] {
+            *it = list->erase(*it);
+        })
+    }
+
+    unsafe fn splice(list: &mut List<Self>, pos: &RawIterator, other: &mut List<Self>) {
+        cpp!([list as "std::list<std::string>*", pos as "const std::list<std::string>::iterator*",
+              other as "std::list<std::string>*"] {
+            list->splice(*pos, *other);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    struct StructWithList {
+        id: i32,
+        lines: List<StlString>,
+    }
+
+    #[test]
+    fn unittest_list() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithList as "struct_with_list*" {
+                static struct_with_list x{7, {"b", "d"}};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.id, 7);
+        assert_eq!(x.lines.len(), 2);
+        let mut lines = unsafe { Pin::new_unchecked(&mut x.lines) };
+        lines.as_mut().push_front("a");
+        lines.as_mut().push_back("e");
+        assert_eq!(
+            lines.iter().map(StlString::to_rust).collect::<Vec<_>>(),
+            vec!["a", "b", "d", "e"]
+        );
+        assert_eq!(
+            lines
+                .iter()
+                .rev()
+                .map(StlString::to_rust)
+                .collect::<Vec<_>>(),
+            vec!["e", "d", "b", "a"]
+        );
+
+        let mut cursor = lines.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_after("c");
+        cursor.move_next();
+        assert_eq!(cursor.current().unwrap().to_rust(), "c");
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some("d".to_owned()));
+        assert_eq!(cursor.current().unwrap().to_rust(), "e");
+        cursor.current().unwrap().assign_str("f");
+
+        let mut other: Pin<Box<List<StlString>>> = vec!["x", "y"].into_iter().collect();
+        cursor.splice_before(other.as_mut());
+        assert!(other.is_empty());
+        cursor.move_next();
+        assert!(cursor.current().is_none());
+        let mut front: Pin<Box<List<StlString>>> = LinkedList::from(["_".to_owned()]).into();
+        cursor.splice_after(front.as_mut());
+
+        assert_eq!(
+            LinkedList::from(&x.lines),
+            LinkedList::from(["_", "a", "b", "c", "x", "y", "f"].map(str::to_owned))
+        );
+
+        let x: &StructWithList = x;
+        let len = unsafe {
+            cpp!([x as "const struct_with_list*"] -> size_t as "size_t" {
+                size_t len = 0;
+                for (const auto& line : x->lines) {
+                    len += line.size();
+                }
+                return len;
+            })
+        };
+        assert_eq!(len, 7);
+    }
+
+    #[test]
+    fn unittest_list_push_pop() {
+        let mut list = List::<StlString>::new();
+        assert_eq!(list.as_mut().pop_front(), None);
+        assert!(list.back().is_none());
+
+        list.as_mut().extend(["1", "2", "3"].iter().copied());
+        assert_eq!(format!("{:?}", list), r#"["1", "2", "3"]"#);
+        assert_eq!(list.as_mut().pop_back(), Some("3".to_owned()));
+        assert_eq!(list.as_mut().pop_front(), Some("1".to_owned()));
+        list.as_mut().front_mut().unwrap().assign_str("two");
+        assert_eq!(list.back().unwrap().to_rust(), "two");
+        assert_eq!(list.len(), 1);
+
+        list.as_mut().clear();
+        assert!(list.is_empty());
+    }
+}
@@ -0,0 +1,1357 @@
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::{PhantomData, PhantomPinned};
+use std::mem::MaybeUninit;
+use std::ops::{Bound, RangeBounds};
+use std::pin::Pin;
+use std::ptr;
+
+use libc::size_t;
+
+use crate::bindings::root::rust::*;
+use crate::element::Element;
+use crate::string::String as StlString;
+use crate::utility::Pair;
+
+cpp! {{
+    #include <map>
+    #include <string>
+    #include <utility>
+
+    #include "wrapper.hpp"
+
+    static_assert(sizeof(std::map<std::string, int32_t>) == sizeof(rust::map_of_dummy), "");
+    static_assert(sizeof(std::map<int32_t, std::string>) == sizeof(rust::map_of_dummy), "");
+    static_assert(
+        sizeof(std::map<std::string, std::pair<int32_t, float>>) == sizeof(rust::map_of_dummy), "");
+    static_assert(
+        sizeof(std::map<std::string, int32_t>::const_iterator) == sizeof(rust::map_iterator_of_dummy), "");
+    static_assert(
+        sizeof(std::map<int32_t, std::string>::const_iterator) == sizeof(rust::map_iterator_of_dummy), "");
+    static_assert(sizeof(std::multimap<std::string, int64_t>) == sizeof(rust::map_of_dummy), "");
+    static_assert(
+        sizeof(std::multimap<std::string, int64_t>::const_iterator) == sizeof(rust::map_iterator_of_dummy), "");
+
+    struct struct_with_maps {
+        int32_t version;
+        std::map<std::string, int32_t> ids;
+        std::map<int32_t, std::string> names;
+    };
+
+    struct struct_with_multimaps {
+        std::multimap<std::string, int64_t> index;
+    };
+}}
+
+/// Opaque `std::map<K, V>::const_iterator`, which is also used for `std::multimap<K, V>`.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct RawIterator(map_iterator_of_dummy);
+
+/// Key types of `std::map<K, V>` for which the instantiation exists in C++.
+pub trait MapKey<V: Element>: Element + Sized {
+    /// Constructs an empty map at `map`.
+    ///
+    /// # Safety
+    ///
+    /// `map` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(map: *mut Map<Self, V>);
+
+    /// Destroys `map`.
+    ///
+    /// # Safety
+    ///
+    /// `map` must not be used after this call.
+    unsafe fn destruct(map: &mut Map<Self, V>);
+
+    fn size(map: &Map<Self, V>) -> usize;
+
+    /// Returns the value of `key`, or null if there is none.
+    fn find(map: &Map<Self, V>, key: &Self::Borrowed) -> *const V;
+
+    /// Returns the value of `key`, inserting a default-constructed one if there is none.
+    fn entry(map: &mut Map<Self, V>, key: &Self::Borrowed) -> *mut V;
+
+    fn erase(map: &mut Map<Self, V>, key: &Self::Borrowed) -> usize;
+
+    fn clear(map: &mut Map<Self, V>);
+
+    fn swap(map: &mut Map<Self, V>, other: &mut Map<Self, V>);
+
+    fn begin(map: &Map<Self, V>, it: &mut RawIterator);
+
+    fn end(map: &Map<Self, V>, it: &mut RawIterator);
+
+    fn lower_bound(map: &Map<Self, V>, key: &Self::Borrowed, it: &mut RawIterator);
+
+    fn upper_bound(map: &Map<Self, V>, key: &Self::Borrowed, it: &mut RawIterator);
+
+    fn equal(it: &RawIterator, other: &RawIterator) -> bool;
+
+    /// Returns the entry at `it` and moves `it` to the next entry.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an entry of a map.
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const V);
+
+    /// Moves `it` to the previous entry and returns it.
+    ///
+    /// # Safety
+    ///
+    /// `it` must not point to the first entry of a map.
+    unsafe fn prev(it: &mut RawIterator) -> (*const Self, *const V);
+}
+
+/// `std::map<K, V>`.
+///
+/// libstdc++ keeps pointers into the map object itself, so a map is pinned and modified
+/// through `Pin<&mut Self>`. A map inside a C++ object may be pinned with
+/// `Pin::new_unchecked`, as C++ does not move it either.
+///
+/// ```compile_fail
+/// use cpp_stl::map::Map;
+/// use cpp_stl::string::String as StlString;
+///
+/// let mut a = Map::<i32, StlString>::new();
+/// let mut b = Map::<i32, StlString>::new();
+/// std::mem::swap(&mut *a, &mut *b);
+/// ```
+#[repr(C)]
+pub struct Map<K: MapKey<V>, V: Element>(map_of_dummy, PhantomData<(K, V)>, PhantomPinned);
+
+impl<K: MapKey<V>, V: Element> Map<K, V> {
+    pub fn new() -> Pin<Box<Self>> {
+        let map = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            K::construct(map);
+            Box::into_pin(Box::from_raw(map))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        K::size(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, key: &K::Borrowed) -> Option<&V> {
+        unsafe { K::find(self, key).as_ref() }
+    }
+
+    /// Returns the value of `key` for modification; it is not named `get_mut` to avoid
+    /// `Pin::get_mut`.
+    pub fn get_value_mut(self: Pin<&mut Self>, key: &K::Borrowed) -> Option<&mut V> {
+        unsafe { (K::find(&self, key) as *mut V).as_mut() }
+    }
+
+    pub fn contains_key(&self, key: &K::Borrowed) -> bool {
+        !K::find(self, key).is_null()
+    }
+
+    /// Inserts `value` for `key`, returning the previous value if there was one.
+    pub fn insert(
+        self: Pin<&mut Self>,
+        key: &K::Borrowed,
+        value: &V::Borrowed,
+    ) -> Option<V::Owned> {
+        let old = self.get(key).map(V::to_rust);
+        unsafe { &mut *K::entry(self.get_unchecked_mut(), key) }.assign(value);
+        old
+    }
+
+    pub fn remove(self: Pin<&mut Self>, key: &K::Borrowed) -> Option<V::Owned> {
+        let old = self.get(key).map(V::to_rust);
+        if old.is_some() {
+            K::erase(unsafe { self.get_unchecked_mut() }, key);
+        }
+        old
+    }
+
+    pub fn clear(self: Pin<&mut Self>) {
+        K::clear(unsafe { self.get_unchecked_mut() });
+    }
+
+    /// Exchanges the contents with `other` without moving either map object.
+    pub fn swap(self: Pin<&mut Self>, other: Pin<&mut Self>) {
+        unsafe { K::swap(self.get_unchecked_mut(), other.get_unchecked_mut()) };
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter::new(self);
+        K::begin(self, &mut iter.front);
+        K::end(self, &mut iter.back);
+        iter
+    }
+
+    /// Returns the entries whose keys are within `range` in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of `range` is greater than its end, or if both are equal and excluded.
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K::Borrowed>,
+        K::Borrowed: Ord,
+    {
+        match (range.start_bound(), range.end_bound()) {
+            (Bound::Excluded(start), Bound::Excluded(end)) if start == end => {
+                panic!("range start and end are equal and excluded in Map")
+            }
+            (Bound::Included(start), Bound::Included(end))
+            | (Bound::Included(start), Bound::Excluded(end))
+            | (Bound::Excluded(start), Bound::Included(end))
+            | (Bound::Excluded(start), Bound::Excluded(end))
+                if start > end =>
+            {
+                panic!("range start is greater than range end in Map")
+            }
+            _ => {}
+        }
+
+        let mut iter = Iter::new(self);
+        match range.start_bound() {
+            Bound::Included(key) => K::lower_bound(self, key, &mut iter.front),
+            Bound::Excluded(key) => K::upper_bound(self, key, &mut iter.front),
+            Bound::Unbounded => K::begin(self, &mut iter.front),
+        }
+        match range.end_bound() {
+            Bound::Included(key) => K::upper_bound(self, key, &mut iter.back),
+            Bound::Excluded(key) => K::lower_bound(self, key, &mut iter.back),
+            Bound::Unbounded => K::end(self, &mut iter.back),
+        }
+        iter
+    }
+}
+
+impl<K: MapKey<V>, V: Element> Drop for Map<K, V> {
+    fn drop(&mut self) {
+        unsafe { K::destruct(self) }
+    }
+}
+
+impl<K, V> fmt::Debug for Map<K, V>
+where
+    K: MapKey<V> + fmt::Debug,
+    V: Element + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K: MapKey<V>, V: Element> IntoIterator for &'a Map<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, Q, R> Extend<(Q, R)> for Pin<&mut Map<K, V>>
+where
+    K: MapKey<V>,
+    V: Element,
+    Q: Borrow<K::Borrowed>,
+    R: Borrow<V::Borrowed>,
+{
+    fn extend<I: IntoIterator<Item = (Q, R)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.as_mut().insert(key.borrow(), value.borrow());
+        }
+    }
+}
+
+impl<K, V, Q, R> FromIterator<(Q, R)> for Pin<Box<Map<K, V>>>
+where
+    K: MapKey<V>,
+    V: Element,
+    Q: Borrow<K::Borrowed>,
+    R: Borrow<V::Borrowed>,
+{
+    fn from_iter<I: IntoIterator<Item = (Q, R)>>(iter: I) -> Self {
+        let mut map = Map::new();
+        map.as_mut().extend(iter);
+        map
+    }
+}
+
+impl<K, V> From<BTreeMap<K::Owned, V::Owned>> for Pin<Box<Map<K, V>>>
+where
+    K: MapKey<V>,
+    V: Element,
+{
+    fn from(map: BTreeMap<K::Owned, V::Owned>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V> From<&Map<K, V>> for BTreeMap<K::Owned, V::Owned>
+where
+    K: MapKey<V>,
+    V: Element,
+    K::Owned: Ord,
+{
+    fn from(map: &Map<K, V>) -> Self {
+        map.iter()
+            .map(|(key, value)| (key.to_rust(), value.to_rust()))
+            .collect()
+    }
+}
+
+pub struct Iter<'a, K: MapKey<V>, V: Element> {
+    map: PhantomData<&'a Map<K, V>>,
+    front: RawIterator,
+    back: RawIterator,
+}
+
+impl<'a, K: MapKey<V>, V: Element> Iter<'a, K, V> {
+    fn new(_map: &'a Map<K, V>) -> Self {
+        Iter {
+            map: PhantomData,
+            front: RawIterator::default(),
+            back: RawIterator::default(),
+        }
+    }
+}
+
+impl<'a, K: MapKey<V>, V: Element> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if K::equal(&self.front, &self.back) {
+            return None;
+        }
+
+        unsafe {
+            let (key, value) = K::next(&mut self.front);
+            Some((&*key, &*value))
+        }
+    }
+}
+
+impl<'a, K: MapKey<V>, V: Element> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if K::equal(&self.front, &self.back) {
+            return None;
+        }
+
+        unsafe {
+            let (key, value) = K::prev(&mut self.back);
+            Some((&*key, &*value))
+        }
+    }
+}
+
+impl MapKey<i32> for StlString {
+    unsafe fn construct(map: *mut Map<Self, i32>) {
+        cpp!([map as "std::map<std::string, int32_t>*"] {
+            new (map) std::map<std::string, int32_t>();
+        })
+    }
+
+    unsafe fn destruct(map: &mut Map<Self, i32>) {
+        cpp!([map as "std::map<std::string, int32_t>*"] {
+            map->~map();
+        })
+    }
+
+    fn size(map: &Map<Self, i32>) -> usize {
+        unsafe {
+            cpp!([map as "const std::map<std::string, int32_t>*"] -> size_t as "size_t" {
+                return map->size();
+            })
+        }
+    }
+
+    fn find(map: &Map<Self, i32>, key: &str) -> *const i32 {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::map<std::string, int32_t>*", ptr as "const char*", len as "size_t"]
+                  -> *const i32 as "const int32_t*" {
+                auto it = map->find(std::string(ptr, len));
+                return it == map->end() ? nullptr : &it->second;
+            })
+        }
+    }
+
+    fn entry(map: &mut Map<Self, i32>, key: &str) -> *mut i32 {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "std::map<std::string, int32_t>*", ptr as "const char*", len as "size_t"]
+                  -> *mut i32 as "int32_t*" {
+                return &(*map)[std::string(ptr, len)];
+            })
+        }
+    }
+
+    fn erase(map: &mut Map<Self, i32>, key: &str) -> usize {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "std::map<std::string, int32_t>*", ptr as "const char*", len as "size_t"]
+                  -> size_t as "size_t" {
+                return map->erase(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn clear(map: &mut Map<Self, i32>) {
+        unsafe {
+            cpp!([map as "std::map<std::string, int32_t>*"] {
+                map->clear();
+            })
+        }
+    }
+
+    fn swap(map: &mut Map<Self, i32>, other: &mut Map<Self, i32>) {
+        unsafe {
+            cpp!([map as "std::map<std::string, int32_t>*", other as "std::map<std::string, int32_t>*"] {
+                map->swap(*other);
+            })
+        }
+    }
+
+    fn begin(map: &Map<Self, i32>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::map<std::string, int32_t>*",
+                  it as "std::map<std::string, int32_t>::const_iterator*"] {
+                *it = map->begin();
+            })
+        }
+    }
+
+    fn end(map: &Map<Self, i32>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::map<std::string, int32_t>*",
+                  it as "std::map<std::string, int32_t>::const_iterator*"] {
+                *it = map->end();
+            })
+        }
+    }
+
+    fn lower_bound(map: &Map<Self, i32>, key: &str, it: &mut RawIterator) {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::map<std::string, int32_t>*", ptr as "const char*", len as "size_t",
+                  it as "std::map<std::string, int32_t>::const_iterator*"] {
+                *it = map->lower_bound(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn upper_bound(map: &Map<Self, i32>, key: &str, it: &mut RawIterator) {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::map<std::string, int32_t>*", ptr as "const char*", len as "size_t",
+                  it as "std::map<std::string, int32_t>::const_iterator*"] {
+                *it = map->upper_bound(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn equal(it: &RawIterator, other: &RawIterator) -> bool {
+        unsafe {
+            cpp!([it as "const std::map<std::string, int32_t>::const_iterator*",
+                  other as "const std::map<std::string, int32_t>::const_iterator*"] -> bool as "bool" {
+                return *it == *other;
+            })
+        }
+    }
+
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const i32) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const i32 = ptr::null();
+        cpp!([it as "std::map<std::string, int32_t>::const_iterator*",
+              mut key as "const std::string*", mut value as "const int32_t*"] {
+            key = &(*it)->first;
+            value = &(*it)->second;
+            ++*it;
+        });
+        (key, value)
+    }
+
+    unsafe fn prev(it: &mut RawIterator) -> (*const Self, *const i32) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const i32 = ptr::null();
+        cpp!([it as "std::map<std::string, int32_t>::const_iterator*",
+              mut key as "const std::string*", mut value as "const int32_t*"] {
+            --*it;
+            key = &(*it)->first;
+            value = &(*it)->second;
+        });
+        (key, value)
+    }
+}
+
+impl MapKey<Pair<i32, f32>> for StlString {
+    unsafe fn construct(map: *mut Map<Self, Pair<i32, f32>>) {
+        cpp!([map as "std::map<std::string, std::pair<int32_t, float>>*"] {
+            new (map) std::map<std::string, std::pair<int32_t, float>>();
+        })
+    }
+
+    unsafe fn destruct(map: &mut Map<Self, Pair<i32, f32>>) {
+        cpp!([map as "std::map<std::string, std::pair<int32_t, float>>*"] {
+            map->~map();
+        })
+    }
+
+    fn size(map: &Map<Self, Pair<i32, f32>>) -> usize {
+        unsafe {
+            cpp!([map as "const std::map<std::string, std::pair<int32_t, float>>*"] -> size_t as "size_t" {
+                return map->size();
+            })
+        }
+    }
+
+    fn find(map: &Map<Self, Pair<i32, f32>>, key: &str) -> *const Pair<i32, f32> {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::map<std::string, std::pair<int32_t, float>>*", ptr as "const char*", len as "size_t"]
+                  -> *const Pair<i32, f32> as "const std::pair<int32_t, float>*" {
+                auto it = map->find(std::string(ptr, len));
+                return it == map->end() ? nullptr : &it->second;
+            })
+        }
+    }
+
+    fn entry(map: &mut Map<Self, Pair<i32, f32>>, key: &str) -> *mut Pair<i32, f32> {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "std::map<std::string, std::pair<int32_t, float>>*", ptr as "const char*", len as "size_t"]
+                  -> *mut Pair<i32, f32> as "std::pair<int32_t, float>*" {
+                return &(*map)[std::string(ptr, len)];
+            })
+        }
+    }
+
+    fn erase(map: &mut Map<Self, Pair<i32, f32>>, key: &str) -> usize {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "std::map<std::string, std::pair<int32_t, float>>*", ptr as "const char*", len as "size_t"]
+                  -> size_t as "size_t" {
+                return map->erase(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn clear(map: &mut Map<Self, Pair<i32, f32>>) {
+        unsafe {
+            cpp!([map as "std::map<std::string, std::pair<int32_t, float>>*"] {
+                map->clear();
+            })
+        }
+    }
+
+    fn swap(map: &mut Map<Self, Pair<i32, f32>>, other: &mut Map<Self, Pair<i32, f32>>) {
+        unsafe {
+            cpp!([map as "std::map<std::string, std::pair<int32_t, float>>*", other as "std::map<std::string, std::pair<int32_t, float>>*"] {
+                map->swap(*other);
+            })
+        }
+    }
+
+    fn begin(map: &Map<Self, Pair<i32, f32>>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::map<std::string, std::pair<int32_t, float>>*",
+                  it as "std::map<std::string, std::pair<int32_t, float>>::const_iterator*"] {
+                *it = map->begin();
+            })
+        }
+    }
+
+    fn end(map: &Map<Self, Pair<i32, f32>>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::map<std::string, std::pair<int32_t, float>>*",
+                  it as "std::map<std::string, std::pair<int32_t, float>>::const_iterator*"] {
+                *it = map->end();
+            })
+        }
+    }
+
+    fn lower_bound(map: &Map<Self, Pair<i32, f32>>, key: &str, it: &mut RawIterator) {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::map<std::string, std::pair<int32_t, float>>*", ptr as "const char*", len as "size_t",
+                  it as "std::map<std::string, std::pair<int32_t, float>>::const_iterator*"] {
+                *it = map->lower_bound(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn upper_bound(map: &Map<Self, Pair<i32, f32>>, key: &str, it: &mut RawIterator) {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::map<std::string, std::pair<int32_t, float>>*", ptr as "const char*", len as "size_t",
+                  it as "std::map<std::string, std::pair<int32_t, float>>::const_iterator*"] {
+                *it = map->upper_bound(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn equal(it: &RawIterator, other: &RawIterator) -> bool {
+        unsafe {
+            cpp!([it as "const std::map<std::string, std::pair<int32_t, float>>::const_iterator*",
+                  other as "const std::map<std::string, std::pair<int32_t, float>>::const_iterator*"] -> bool as "bool" {
+                return *it == *other;
+            })
+        }
+    }
+
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const Pair<i32, f32>) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const Pair<i32, f32> = ptr::null();
+        cpp!([it as "std::map<std::string, std::pair<int32_t, float>>::const_iterator*",
+              mut key as "const std::string*", mut value as "const std::pair<int32_t, float>*"] {
+            key = &(*it)->first;
+            value = &(*it)->second;
+            ++*it;
+        });
+        (key, value)
+    }
+
+    unsafe fn prev(it: &mut RawIterator) -> (*const Self, *const Pair<i32, f32>) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const Pair<i32, f32> = ptr::null();
+        cpp!([it as "std::map<std::string, std::pair<int32_t, float>>::const_iterator*",
+              mut key as "const std::string*", mut value as "const std::pair<int32_t, float>*"] {
+            --*it;
+            key = &(*it)->first;
+            value = &(*it)->second;
+        });
+        (key, value)
+    }
+}
+
+impl MapKey<StlString> for i32 {
+    unsafe fn construct(map: *mut Map<Self, StlString>) {
+        cpp!([map as "std::map<int32_t, std::string>*"] {
+            new (map) std::map<int32_t, std::string>();
+        })
+    }
+
+    unsafe fn destruct(map: &mut Map<Self, StlString>) {
+        cpp!([map as "std::map<int32_t, std::string>*"] {
+            map->~map();
+        })
+    }
+
+    fn size(map: &Map<Self, StlString>) -> usize {
+        unsafe {
+            cpp!([map as "const std::map<int32_t, std::string>*"] -> size_t as "size_t" {
+                return map->size();
+            })
+        }
+    }
+
+    fn find(map: &Map<Self, StlString>, key: &i32) -> *const StlString {
+        let key = *key;
+        unsafe {
+            cpp!([map as "const std::map<int32_t, std::string>*", key as "int32_t"]
+                  -> *const StlString as "const std::string*" {
+                auto it = map->find(key);
+                return it == map->end() ? nullptr : &it->second;
+            })
+        }
+    }
+
+    fn entry(map: &mut Map<Self, StlString>, key: &i32) -> *mut StlString {
+        let key = *key;
+        unsafe {
+            cpp!([map as "std::map<int32_t, std::string>*", key as "int32_t"]
+                  -> *mut StlString as "std::string*" {
+                return &(*map)[key];
+            })
+        }
+    }
+
+    fn erase(map: &mut Map<Self, StlString>, key: &i32) -> usize {
+        let key = *key;
+        unsafe {
+            cpp!([map as "std::map<int32_t, std::string>*", key as "int32_t"] -> size_t as "size_t" {
+                return map->erase(key);
+            })
+        }
+    }
+
+    fn clear(map: &mut Map<Self, StlString>) {
+        unsafe {
+            cpp!([map as "std::map<int32_t, std::string>*"] {
+                map->clear();
+            })
+        }
+    }
+
+    fn swap(map: &mut Map<Self, StlString>, other: &mut Map<Self, StlString>) {
+        unsafe {
+            cpp!([map as "std::map<int32_t, std::string>*", other as "std::map<int32_t, std::string>*"] {
+                map->swap(*other);
+            })
+        }
+    }
+
+    fn begin(map: &Map<Self, StlString>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::map<int32_t, std::string>*",
+                  it as "std::map<int32_t, std::string>::const_iterator*"] {
+                *it = map->begin();
+            })
+        }
+    }
+
+    fn end(map: &Map<Self, StlString>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::map<int32_t, std::string>*",
+                  it as "std::map<int32_t, std::string>::const_iterator*"] {
+                *it = map->end();
+            })
+        }
+    }
+
+    fn lower_bound(map: &Map<Self, StlString>, key: &i32, it: &mut RawIterator) {
+        let key = *key;
+        unsafe {
+            cpp!([map as "const std::map<int32_t, std::string>*", key as "int32_t",
+                  it as "std::map<int32_t, std::string>::const_iterator*"] {
+                *it = map->lower_bound(key);
+            })
+        }
+    }
+
+    fn upper_bound(map: &Map<Self, StlString>, key: &i32, it: &mut RawIterator) {
+        let key = *key;
+        unsafe {
+            cpp!([map as "const std::map<int32_t, std::string>*", key as "int32_t",
+                  it as "std::map<int32_t, std::string>::const_iterator*"] {
+                *it = map->upper_bound(key);
+            })
+        }
+    }
+
+    fn equal(it: &RawIterator, other: &RawIterator) -> bool {
+        unsafe {
+            cpp!([it as "const std::map<int32_t, std::string>::const_iterator*",
+                  other as "const std::map<int32_t, std::string>::const_iterator*"] -> bool as "bool" {
+                return *it == *other;
+            })
+        }
+    }
+
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const StlString) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const StlString = ptr::null();
+        cpp!([it as "std::map<int32_t, std::string>::const_iterator*",
+              mut key as "const int32_t*", mut value as "const std::string*"] {
+            key = &(*it)->first;
+            value = &(*it)->second;
+            ++*it;
+        });
+        (key, value)
+    }
+
+    unsafe fn prev(it: &mut RawIterator) -> (*const Self, *const StlString) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const StlString = ptr::null();
+        cpp!([it as "std::map<int32_t, std::string>::const_iterator*",
+              mut key as "const int32_t*", mut value as "const std::string*"] {
+            --*it;
+            key = &(*it)->first;
+            value = &(*it)->second;
+        });
+        (key, value)
+    }
+}
+
+/// Key types of `std::multimap<K, V>` for which the instantiation exists in C++.
+pub trait MultiMapKey<V: Element>: Element + Sized {
+    /// Constructs an empty multimap at `map`.
+    ///
+    /// # Safety
+    ///
+    /// `map` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(map: *mut MultiMap<Self, V>);
+
+    /// Destroys `map`.
+    ///
+    /// # Safety
+    ///
+    /// `map` must not be used after this call.
+    unsafe fn destruct(map: &mut MultiMap<Self, V>);
+
+    fn size(map: &MultiMap<Self, V>) -> usize;
+
+    fn count(map: &MultiMap<Self, V>, key: &Self::Borrowed) -> usize;
+
+    /// Inserts an entry after the entries with an equivalent key.
+    fn insert(map: &mut MultiMap<Self, V>, key: &Self::Borrowed, value: &V::Borrowed);
+
+    fn erase(map: &mut MultiMap<Self, V>, key: &Self::Borrowed) -> usize;
+
+    /// Removes the entry at `it`.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an entry of `map`.
+    unsafe fn erase_at(map: &mut MultiMap<Self, V>, it: &RawIterator);
+
+    fn clear(map: &mut MultiMap<Self, V>);
+
+    fn begin(map: &MultiMap<Self, V>, it: &mut RawIterator);
+
+    fn end(map: &MultiMap<Self, V>, it: &mut RawIterator);
+
+    fn equal_range(
+        map: &MultiMap<Self, V>,
+        key: &Self::Borrowed,
+        first: &mut RawIterator,
+        last: &mut RawIterator,
+    );
+
+    fn equal(it: &RawIterator, other: &RawIterator) -> bool;
+
+    /// Returns the entry at `it` and moves `it` to the next entry.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an entry of a multimap.
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const V);
+
+    /// Moves `it` to the previous entry and returns it.
+    ///
+    /// # Safety
+    ///
+    /// `it` must not point to the first entry of a multimap.
+    unsafe fn prev(it: &mut RawIterator) -> (*const Self, *const V);
+}
+
+/// `std::multimap<K, V>`, whose entries with equivalent keys are kept in insertion order.
+///
+/// It is laid out as `std::map<K, V>`, so it is pinned like `Map`.
+#[repr(C)]
+pub struct MultiMap<K: MultiMapKey<V>, V: Element>(
+    map_of_dummy,
+    PhantomData<(K, V)>,
+    PhantomPinned,
+);
+
+impl<K: MultiMapKey<V>, V: Element> MultiMap<K, V> {
+    pub fn new() -> Pin<Box<Self>> {
+        let map = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            K::construct(map);
+            Box::into_pin(Box::from_raw(map))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        K::size(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of entries for `key`.
+    pub fn count(&self, key: &K::Borrowed) -> usize {
+        K::count(self, key)
+    }
+
+    pub fn contains_key(&self, key: &K::Borrowed) -> bool {
+        self.count(key) != 0
+    }
+
+    /// Returns the first value of `key`.
+    pub fn get(&self, key: &K::Borrowed) -> Option<&V> {
+        self.equal_range(key).next().map(|(_, value)| value)
+    }
+
+    pub fn insert(self: Pin<&mut Self>, key: &K::Borrowed, value: &V::Borrowed) {
+        K::insert(unsafe { self.get_unchecked_mut() }, key, value);
+    }
+
+    /// Removes every entry for `key`, returning how many there were.
+    pub fn remove_all(self: Pin<&mut Self>, key: &K::Borrowed) -> usize {
+        K::erase(unsafe { self.get_unchecked_mut() }, key)
+    }
+
+    /// Removes the first entry of `key` whose value equals `value`, returning whether one
+    /// was found.
+    pub fn remove_entry(self: Pin<&mut Self>, key: &K::Borrowed, value: &V::Borrowed) -> bool
+    where
+        V::Borrowed: PartialEq,
+    {
+        let mut iter = self.equal_range(key);
+        loop {
+            let it = iter.front;
+            match iter.next() {
+                Some((_, found)) if Borrow::<V::Borrowed>::borrow(&found.to_rust()) == value => {
+                    unsafe { K::erase_at(self.get_unchecked_mut(), &it) };
+                    return true;
+                }
+                Some(_) => {}
+                None => return false,
+            }
+        }
+    }
+
+    pub fn clear(self: Pin<&mut Self>) {
+        K::clear(unsafe { self.get_unchecked_mut() });
+    }
+
+    /// Returns the entries in ascending key order.
+    pub fn iter(&self) -> MultiIter<'_, K, V> {
+        let mut iter = MultiIter::new(self);
+        K::begin(self, &mut iter.front);
+        K::end(self, &mut iter.back);
+        iter
+    }
+
+    /// Returns the entries for `key` in insertion order.
+    pub fn equal_range(&self, key: &K::Borrowed) -> MultiIter<'_, K, V> {
+        let mut iter = MultiIter::new(self);
+        K::equal_range(self, key, &mut iter.front, &mut iter.back);
+        iter
+    }
+}
+
+impl<K: MultiMapKey<V>, V: Element> Drop for MultiMap<K, V> {
+    fn drop(&mut self) {
+        unsafe { K::destruct(self) }
+    }
+}
+
+impl<K, V> fmt::Debug for MultiMap<K, V>
+where
+    K: MultiMapKey<V> + fmt::Debug,
+    V: Element + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K: MultiMapKey<V>, V: Element> IntoIterator for &'a MultiMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MultiIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, Q, R> Extend<(Q, R)> for Pin<&mut MultiMap<K, V>>
+where
+    K: MultiMapKey<V>,
+    V: Element,
+    Q: Borrow<K::Borrowed>,
+    R: Borrow<V::Borrowed>,
+{
+    fn extend<I: IntoIterator<Item = (Q, R)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.as_mut().insert(key.borrow(), value.borrow());
+        }
+    }
+}
+
+impl<K, V, Q, R> FromIterator<(Q, R)> for Pin<Box<MultiMap<K, V>>>
+where
+    K: MultiMapKey<V>,
+    V: Element,
+    Q: Borrow<K::Borrowed>,
+    R: Borrow<V::Borrowed>,
+{
+    fn from_iter<I: IntoIterator<Item = (Q, R)>>(iter: I) -> Self {
+        let mut map = MultiMap::new();
+        map.as_mut().extend(iter);
+        map
+    }
+}
+
+impl<K, V> From<&MultiMap<K, V>> for Vec<(K::Owned, V::Owned)>
+where
+    K: MultiMapKey<V>,
+    V: Element,
+{
+    fn from(map: &MultiMap<K, V>) -> Self {
+        map.iter()
+            .map(|(key, value)| (key.to_rust(), value.to_rust()))
+            .collect()
+    }
+}
+
+pub struct MultiIter<'a, K: MultiMapKey<V>, V: Element> {
+    map: PhantomData<&'a MultiMap<K, V>>,
+    front: RawIterator,
+    back: RawIterator,
+}
+
+impl<'a, K: MultiMapKey<V>, V: Element> MultiIter<'a, K, V> {
+    fn new(_map: &'a MultiMap<K, V>) -> Self {
+        MultiIter {
+            map: PhantomData,
+            front: RawIterator::default(),
+            back: RawIterator::default(),
+        }
+    }
+}
+
+impl<'a, K: MultiMapKey<V>, V: Element> Iterator for MultiIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if K::equal(&self.front, &self.back) {
+            return None;
+        }
+
+        unsafe {
+            let (key, value) = K::next(&mut self.front);
+            Some((&*key, &*value))
+        }
+    }
+}
+
+impl<'a, K: MultiMapKey<V>, V: Element> DoubleEndedIterator for MultiIter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if K::equal(&self.front, &self.back) {
+            return None;
+        }
+
+        unsafe {
+            let (key, value) = K::prev(&mut self.back);
+            Some((&*key, &*value))
+        }
+    }
+}
+
+impl MultiMapKey<i64> for StlString {
+    unsafe fn construct(map: *mut MultiMap<Self, i64>) {
+        cpp!([map as "std::multimap<std::string, int64_t>*"] {
+            new (map) std::multimap<std::string, int64_t>();
+        })
+    }
+
+    unsafe fn destruct(map: &mut MultiMap<Self, i64>) {
+        cpp!([map as "std::multimap<std::string, int64_t>*"] {
+            map->~multimap();
+        })
+    }
+
+    fn size(map: &MultiMap<Self, i64>) -> usize {
+        unsafe {
+            cpp!([map as "const std::multimap<std::string, int64_t>*"] -> size_t as "size_t" {
+                return map->size();
+            })
+        }
+    }
+
+    fn count(map: &MultiMap<Self, i64>, key: &str) -> usize {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::multimap<std::string, int64_t>*", ptr as "const char*", len as "size_t"]
+                  -> size_t as "size_t" {
+                return map->count(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn insert(map: &mut MultiMap<Self, i64>, key: &str, value: &i64) {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        let value = *value;
+        unsafe {
+            cpp!([map as "std::multimap<std::string, int64_t>*", ptr as "const char*", len as "size_t",
+                  value as "int64_t"] {
+                map->emplace(std::string(ptr, len), value);
+            })
+        }
+    }
+
+    fn erase(map: &mut MultiMap<Self, i64>, key: &str) -> usize {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "std::multimap<std::string, int64_t>*", ptr as "const char*", len as "size_t"]
+                  -> size_t as "size_t" {
+                return map->erase(std::string(ptr, len));
+            })
+        }
+    }
+
+    unsafe fn erase_at(map: &mut MultiMap<Self, i64>, it: &RawIterator) {
+        cpp!([map as "std::multimap<std::string, int64_t>*",
+              it as "const std::multimap<std::string, int64_t>::const_iterator*"] {
+            map->erase(*it);
+        })
+    }
+
+    fn clear(map: &mut MultiMap<Self, i64>) {
+        unsafe {
+            cpp!([map as "std::multimap<std::string, int64_t>*"] {
+                map->clear();
+            })
+        }
+    }
+
+    fn begin(map: &MultiMap<Self, i64>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::multimap<std::string, int64_t>*",
+                  it as "std::multimap<std::string, int64_t>::const_iterator*"] {
+                *it = map->begin();
+            })
+        }
+    }
+
+    fn end(map: &MultiMap<Self, i64>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::multimap<std::string, int64_t>*",
+                  it as "std::multimap<std::string, int64_t>::const_iterator*"] {
+                *it = map->end();
+            })
+        }
+    }
+
+    fn equal_range(
+        map: &MultiMap<Self, i64>,
+        key: &str,
+        first: &mut RawIterator,
+        last: &mut RawIterator,
+    ) {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::multimap<std::string, int64_t>*", ptr as "const char*", len as "size_t",
+                  first as "std::multimap<std::string, int64_t>::const_iterator*",
+                  last as "std::multimap<std::string, int64_t>::const_iterator*"] {
+                std::tie(*first, *last) = map->equal_range(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn equal(it: &RawIterator, other: &RawIterator) -> bool {
+        unsafe {
+            cpp!([it as "const std::multimap<std::string, int64_t>::const_iterator*",
+                  other as "const std::multimap<std::string, int64_t>::const_iterator*"] -> bool as "bool" {
+                return *it == *other;
+            })
+        }
+    }
+
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const i64) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const i64 = ptr::null();
+        cpp!([it as "std::multimap<std::string, int64_t>::const_iterator*",
+              mut key as "const std::string*", mut value as "const int64_t*"] {
+            key = &(*it)->first;
+            value = &(*it)->second;
+            ++*it;
+        });
+        (key, value)
+    }
+
+    unsafe fn prev(it: &mut RawIterator) -> (*const Self, *const i64) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const i64 = ptr::null();
+        cpp!([it as "std::multimap<std::string, int64_t>::const_iterator*",
+              mut key as "const std::string*", mut value as "const int64_t*"] {
+            --*it;
+            key = &(*it)->first;
+            value = &(*it)->second;
+        });
+        (key, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    struct StructWithMaps {
+        version: i32,
+        ids: Map<StlString, i32>,
+        names: Map<i32, StlString>,
+    }
+
+    #[test]
+    fn unittest_map() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithMaps as "struct_with_maps*" {
+                static struct_with_maps x{
+                    3, {{"bora", 7}, {"boncheol", 23}}, {{10, "seoul"}, {30, "busan"}, {20, "seongnam"}}
+                };
+                return &x;
+            })
+        };
+
+        assert_eq!(x.version, 3);
+        assert_eq!(x.ids.len(), 2);
+        assert_eq!(x.ids.get("boncheol"), Some(&23));
+        assert_eq!(x.ids.get("junmo"), None);
+        assert!(x.ids.contains_key("bora"));
+        assert_eq!(
+            x.ids
+                .iter()
+                .map(|(key, value)| (key.to_rust(), *value))
+                .collect::<Vec<_>>(),
+            vec![("boncheol".to_owned(), 23), ("bora".to_owned(), 7)]
+        );
+
+        let mut ids = unsafe { Pin::new_unchecked(&mut x.ids) };
+        *ids.as_mut().get_value_mut("bora").unwrap() = 8;
+        assert_eq!(ids.as_mut().insert("junmo", &31), None);
+        assert_eq!(ids.as_mut().insert("boncheol", &24), Some(23));
+        assert_eq!(ids.as_mut().remove("nobody"), None);
+        assert_eq!(ids.as_mut().remove("bora"), Some(8));
+        assert_eq!(
+            BTreeMap::from(&x.ids),
+            vec![("boncheol".to_owned(), 24), ("junmo".to_owned(), 31)]
+                .into_iter()
+                .collect()
+        );
+
+        assert_eq!(
+            x.names.iter().map(|(key, _)| *key).collect::<Vec<_>>(),
+            vec![10, 20, 30]
+        );
+        assert_eq!(
+            x.names
+                .iter()
+                .rev()
+                .map(|(key, _)| *key)
+                .collect::<Vec<_>>(),
+            vec![30, 20, 10]
+        );
+        assert_eq!(
+            x.names.range(15..).map(|(key, _)| *key).collect::<Vec<_>>(),
+            vec![20, 30]
+        );
+        assert_eq!(
+            x.names
+                .range(..=20)
+                .map(|(key, _)| *key)
+                .collect::<Vec<_>>(),
+            vec![10, 20]
+        );
+        assert_eq!(x.names.range(11..20).count(), 0);
+        assert_eq!(x.names.get(&20).unwrap().to_rust(), "seongnam");
+
+        let mut names: Pin<Box<Map<i32, StlString>>> =
+            vec![(1, "one"), (2, "two")].into_iter().collect();
+        names.as_mut().insert(&3, "three");
+        unsafe { Pin::new_unchecked(&mut x.names) }.swap(names.as_mut());
+        assert_eq!(names.len(), 3);
+        assert_eq!(
+            BTreeMap::from(&*names).into_keys().collect::<Vec<_>>(),
+            vec![10, 20, 30]
+        );
+
+        let x: &StructWithMaps = x;
+        let len = unsafe {
+            cpp!([x as "const struct_with_maps*"] -> size_t as "size_t" {
+                return x->names.at(3).size() + x->ids.at("junmo");
+            })
+        };
+        assert_eq!(len, 36);
+
+        let ids: BTreeMap<String, i32> = vec![("a".to_owned(), 1), ("b".to_owned(), 2)]
+            .into_iter()
+            .collect();
+        let map: Pin<Box<Map<StlString, i32>>> = ids.clone().into();
+        assert_eq!(format!("{:?}", map), r#"{"a": 1, "b": 2}"#);
+        assert_eq!(BTreeMap::from(&*map), ids);
+    }
+
+    #[repr(C)]
+    struct StructWithMultiMaps {
+        index: MultiMap<StlString, i64>,
+    }
+
+    #[test]
+    fn unittest_multimap() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithMultiMaps as "struct_with_multimaps*" {
+                static struct_with_multimaps x{{{"seoul", 3}, {"busan", 1}, {"seoul", 1}, {"seoul", 2}}};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.index.len(), 4);
+        assert_eq!(x.index.count("seoul"), 3);
+        assert_eq!(x.index.count("daegu"), 0);
+        assert_eq!(x.index.get("seoul"), Some(&3));
+        assert_eq!(
+            x.index
+                .equal_range("seoul")
+                .map(|(_, value)| *value)
+                .collect::<Vec<_>>(),
+            vec![3, 1, 2]
+        );
+        assert_eq!(x.index.equal_range("daegu").count(), 0);
+
+        let mut index = unsafe { Pin::new_unchecked(&mut x.index) };
+        index.as_mut().insert("seoul", &1);
+        index.as_mut().insert("daegu", &5);
+        assert!(index.as_mut().remove_entry("seoul", &1));
+        assert!(!index.as_mut().remove_entry("busan", &2));
+        assert_eq!(
+            index
+                .equal_range("seoul")
+                .rev()
+                .map(|(_, value)| *value)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(index.as_mut().remove_all("busan"), 1);
+        assert_eq!(index.as_mut().remove_all("busan"), 0);
+
+        let x: &StructWithMultiMaps = x;
+        let sum = unsafe {
+            cpp!([x as "const struct_with_multimaps*"] -> i64 as "int64_t" {
+                int64_t sum = 0;
+                for (const auto& entry : x->index) {
+                    sum = sum * 10 + entry.second;
+                }
+                return sum;
+            })
+        };
+        assert_eq!(sum, 5321);
+        assert_eq!(
+            Vec::from(&x.index),
+            vec![
+                ("daegu".to_owned(), 5),
+                ("seoul".to_owned(), 3),
+                ("seoul".to_owned(), 2),
+                ("seoul".to_owned(), 1)
+            ]
+        );
+
+        let mut map: Pin<Box<MultiMap<StlString, i64>>> =
+            vec![("b", 1), ("a", 2), ("b", 3)].into_iter().collect();
+        assert_eq!(format!("{:?}", map), r#"{"a": 2, "b": 1, "b": 3}"#);
+        map.as_mut().clear();
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn unittest_map_invalid_range() {
+        let map: Pin<Box<Map<i32, StlString>>> = Map::new();
+        map.range((Bound::Included(2), Bound::Included(1)));
+    }
+}
@@ -0,0 +1,153 @@
+//! Types which the STL members in bindings generated by `cpp_stl_build::Generator` are
+//! mapped to.
+//!
+//! The generated `root::std` module declares `vector<T, A>` as `Vector<T, A>` and so on,
+//! which resolve to the binding of the instantiation.
+
+use std::marker::PhantomData;
+
+use crate::memory;
+use crate::string::String as StlString;
+use crate::utility::Pair;
+use crate::vector::*;
+
+/// `std::allocator<T>`, which only appears as a template argument.
+pub struct Allocator<T>(PhantomData<T>);
+
+/// `std::default_delete<T>`, which only appears as a template argument.
+pub struct DefaultDelete<T>(PhantomData<T>);
+
+/// Element types of `std::vector<T, A>` which have a binding.
+pub trait VectorMapping<A> {
+    type Vector;
+}
+
+/// `std::vector<T, A>`.
+pub type Vector<T, A> = <T as VectorMapping<A>>::Vector;
+
+/// Deleters of `std::unique_ptr<T, D>` which have a binding.
+pub trait DeleterMapping<T> {
+    type UniquePtr;
+}
+
+/// `std::unique_ptr<T, D>`.
+pub type UniquePtr<T, D> = <D as DeleterMapping<T>>::UniquePtr;
+
+impl<T> DeleterMapping<T> for DefaultDelete<T> {
+    type UniquePtr = memory::UniquePtr<T>;
+}
+
+macro_rules! add_impl {
+    ($($t:ty => $vector:ty,)*) => ($(
+        impl<A> VectorMapping<A> for $t {
+            type Vector = $vector;
+        }
+    )*)
+}
+
+add_impl! {
+    u8 => VectorOfU8,
+    i32 => VectorOfI32,
+    i64 => VectorOfI64,
+    f32 => VectorOfF32,
+    Pair<i32, f32> => VectorOfPairI32F32,
+    StlString => VectorOfString,
+    VectorOfI32 => VectorOfVector<VectorOfI32>,
+    VectorOfF32 => VectorOfVector<VectorOfF32>,
+}
+
+#[cfg(feature = "tuple")]
+impl<A> VectorMapping<A> for crate::tuple::Tuple3<i32, f32, f64> {
+    type Vector = VectorOfTupleI32F32F64;
+}
+
+impl<A, T> VectorMapping<A> for memory::UniquePtr<T> {
+    type Vector = VectorOfUniquePtr<T>;
+}
+
+impl<A, T: PodElement> VectorMapping<A> for T {
+    type Vector = VectorOf<T>;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vector::VectorSlice;
+
+    // What `cpp_stl_build::Generator` generates for `struct_with_vectors` of the vector tests,
+    // which `cpp_stl_example` checks by running it where libclang is found.
+    #[allow(non_camel_case_types, dead_code, clippy::type_complexity)]
+    mod root {
+        use self::super::root;
+        use crate::mirror::CppMirror;
+
+        pub mod std {
+            pub type string = ::cpp_stl::string::String;
+            pub type allocator<T> = ::cpp_stl::mapping::Allocator<T>;
+            pub type vector<T, A = allocator<T>> = ::cpp_stl::mapping::Vector<T, A>;
+            pub type default_delete<T> = ::cpp_stl::mapping::DefaultDelete<T>;
+            pub type unique_ptr<T, D = default_delete<T>> = ::cpp_stl::mapping::UniquePtr<T, D>;
+            pub type pair<T1, T2> = ::cpp_stl::utility::Pair<T1, T2>;
+        }
+
+        #[derive(CppMirror)]
+        #[cpp_mirror("name")]
+        #[repr(C)]
+        pub struct name {
+            pub first_name: root::std::string,
+            pub last_name: root::std::string,
+        }
+
+        #[derive(CppMirror)]
+        #[cpp_mirror("superclass")]
+        #[repr(C)]
+        pub struct superclass {
+            pub dummy: i32,
+        }
+
+        #[derive(CppMirror)]
+        #[cpp_mirror("subclass")]
+        #[repr(C)]
+        pub struct subclass {
+            #[cpp_mirror(skip)]
+            pub _base: root::superclass,
+            pub value: i32,
+            pub desc: root::std::string,
+        }
+
+        #[derive(CppMirror)]
+        #[cpp_mirror("struct_with_vectors")]
+        #[repr(C)]
+        pub struct struct_with_vectors {
+            pub ids: root::std::vector<i32, root::std::allocator<i32>>,
+            pub names: root::std::vector<
+                root::std::unique_ptr<root::name, root::std::default_delete<root::name>>,
+                root::std::allocator<
+                    root::std::unique_ptr<root::name, root::std::default_delete<root::name>>,
+                >,
+            >,
+            pub ages: root::std::vector<i32>,
+            pub cities: root::std::vector<root::std::unique_ptr<root::std::string>>,
+            pub derived: root::std::vector<root::std::unique_ptr<root::subclass>>,
+        }
+    }
+
+    #[test]
+    fn unittest_mapping() {
+        let x = unsafe {
+            cpp!([] -> &root::struct_with_vectors as "struct_with_vectors*" {
+                static struct_with_vectors x{{1, 2}, {}, {30}, {}, {}};
+                x.names.emplace_back(new name{"boncheol", "gu"});
+                x.cities.emplace_back(new std::string("seoul"));
+                x.derived.emplace_back(new subclass(7, "derived"));
+                return &x;
+            })
+        };
+
+        assert_eq!(x.ids.as_slice(), &[1, 2]);
+        assert_eq!(x.ages.as_slice(), &[30]);
+        assert_eq!(x.names[0].last_name.to_string(), "gu");
+        assert_eq!(x.cities[0].to_string(), "seoul");
+        assert_eq!(x.derived[0].value, 7);
+        assert_eq!(x.derived[0].desc.to_string(), "derived");
+    }
+}
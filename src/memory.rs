@@ -1,12 +1,17 @@
 use std::ffi::c_void;
 use std::fmt;
 use std::marker::PhantomData;
+use std::mem::MaybeUninit;
 use std::ops::{Deref, DerefMut};
+use std::{mem, ptr, slice};
+
+use libc::size_t;
 
 use crate::bindings::root::rust::*;
 
 cpp! {{
     #include <memory>
+    #include <string>
 
     struct class_with_unique_ptr {
         std::string desc;
@@ -14,11 +19,47 @@ cpp! {{
 
         class_with_unique_ptr(int32_t v, const char* s): desc(s), value(new int32_t(v)) {}
     };
+
+    struct struct_with_unique_array {
+        std::unique_ptr<float[]> values;
+        size_t len;
+    };
 }}
 
+/// `std::unique_ptr<T>`, laid out as `std::unique_ptr<void>`.
+///
+/// Dropping it does not delete the pointee, which only C++ knows how to do; `reset` does
+/// for the pointee types implementing `UniquePtrElement`.
 #[repr(C)]
 pub struct UniquePtr<T>(unique_ptr_of_void, PhantomData<T>);
 
+impl<T> UniquePtr<T> {
+    /// Takes ownership of `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null or allocated by C++ `new` and not owned by anything else.
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        let mut unique_ptr = MaybeUninit::<Self>::uninit();
+        let raw = unique_ptr.as_mut_ptr();
+        let ptr = ptr as *mut c_void;
+        cpp!([raw as "std::unique_ptr<void>*", ptr as "void*"] {
+            new (raw) std::unique_ptr<void>(ptr);
+        });
+        unique_ptr.assume_init()
+    }
+
+    /// Releases ownership of the pointee, which the caller must delete in C++.
+    pub fn into_raw(self) -> *mut T {
+        let unique_ptr = &self;
+        unsafe {
+            cpp!([unique_ptr as "const std::unique_ptr<void>*"] -> *mut c_void as "void*" {
+                return unique_ptr->get();
+            }) as *mut T
+        }
+    }
+}
+
 impl<T> fmt::Debug for UniquePtr<T>
 where
     T: fmt::Debug,
@@ -54,11 +95,252 @@ impl<T> DerefMut for UniquePtr<T> {
     }
 }
 
-#[cfg(test)]
+/// Pointee types of `std::unique_ptr<T>` which can be deleted from Rust.
+pub trait UniquePtrElement: Sized {
+    /// Destroys the pointee of `ptr` with `delete`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null or allocated by C++ `new` and not owned by anything else.
+    unsafe fn delete(ptr: *mut Self);
+}
+
+impl UniquePtrElement for u8 {
+    unsafe fn delete(ptr: *mut Self) {
+        cpp!([ptr as "uint8_t*"] {
+            delete ptr;
+        })
+    }
+}
+
+impl UniquePtrElement for i32 {
+    unsafe fn delete(ptr: *mut Self) {
+        cpp!([ptr as "int32_t*"] {
+            delete ptr;
+        })
+    }
+}
+
+impl UniquePtrElement for i64 {
+    unsafe fn delete(ptr: *mut Self) {
+        cpp!([ptr as "int64_t*"] {
+            delete ptr;
+        })
+    }
+}
+
+impl UniquePtrElement for f32 {
+    unsafe fn delete(ptr: *mut Self) {
+        cpp!([ptr as "float*"] {
+            delete ptr;
+        })
+    }
+}
+
+impl<T: UniquePtrElement> UniquePtr<T> {
+    /// Deletes the pointee and leaves the pointer null, like `std::unique_ptr<T>::reset()`.
+    pub fn reset(&mut self) {
+        let old = mem::replace(self, unsafe { Self::from_raw(ptr::null_mut()) });
+        unsafe { T::delete(old.into_raw()) }
+    }
+}
+
+/// Element types of `std::unique_ptr<T[]>` which can be allocated and released from Rust.
+pub trait ArrayElement: Copy {
+    /// Constructs at `array` a `std::unique_ptr<T[]>` owning `len` value-initialized elements.
+    ///
+    /// # Safety
+    ///
+    /// `array` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn new_array(array: *mut UniqueArray<Self>, len: usize);
+
+    /// Releases the elements owned by `array` with `delete[]`, leaving it empty.
+    ///
+    /// # Safety
+    ///
+    /// `array` must own its elements, as made by `new_array` or by C++ with its length.
+    unsafe fn delete_array(array: &mut UniqueArray<Self>);
+}
+
+impl ArrayElement for u8 {
+    unsafe fn new_array(array: *mut UniqueArray<Self>, len: usize) {
+        let len = len as size_t;
+        cpp!([array as "std::unique_ptr<uint8_t[]>*", len as "size_t"] {
+            new (array) std::unique_ptr<uint8_t[]>(new uint8_t[len]());
+        })
+    }
+
+    unsafe fn delete_array(array: &mut UniqueArray<Self>) {
+        cpp!([array as "std::unique_ptr<uint8_t[]>*"] {
+            array->reset();
+        });
+        array.len = 0;
+    }
+}
+
+impl ArrayElement for i32 {
+    unsafe fn new_array(array: *mut UniqueArray<Self>, len: usize) {
+        let len = len as size_t;
+        cpp!([array as "std::unique_ptr<int32_t[]>*", len as "size_t"] {
+            new (array) std::unique_ptr<int32_t[]>(new int32_t[len]());
+        })
+    }
+
+    unsafe fn delete_array(array: &mut UniqueArray<Self>) {
+        cpp!([array as "std::unique_ptr<int32_t[]>*"] {
+            array->reset();
+        });
+        array.len = 0;
+    }
+}
+
+impl ArrayElement for i64 {
+    unsafe fn new_array(array: *mut UniqueArray<Self>, len: usize) {
+        let len = len as size_t;
+        cpp!([array as "std::unique_ptr<int64_t[]>*", len as "size_t"] {
+            new (array) std::unique_ptr<int64_t[]>(new int64_t[len]());
+        })
+    }
+
+    unsafe fn delete_array(array: &mut UniqueArray<Self>) {
+        cpp!([array as "std::unique_ptr<int64_t[]>*"] {
+            array->reset();
+        });
+        array.len = 0;
+    }
+}
+
+impl ArrayElement for f32 {
+    unsafe fn new_array(array: *mut UniqueArray<Self>, len: usize) {
+        let len = len as size_t;
+        cpp!([array as "std::unique_ptr<float[]>*", len as "size_t"] {
+            new (array) std::unique_ptr<float[]>(new float[len]());
+        })
+    }
+
+    unsafe fn delete_array(array: &mut UniqueArray<Self>) {
+        cpp!([array as "std::unique_ptr<float[]>*"] {
+            array->reset();
+        });
+        array.len = 0;
+    }
+}
+
+/// `std::unique_ptr<T[]>` followed by the number of its elements.
+///
+/// C++ does not keep the length of the array, so this is not a mirror of
+/// `std::unique_ptr<T[]>` and cannot stand for one embedded in a C++ struct, which is a
+/// `size_t` smaller. It only matches the layout of a `std::unique_ptr<T[]>` member
+/// immediately followed by a `size_t` length member, e.g.
+/// `struct { std::unique_ptr<float[]> values; size_t len; }`.
+/// Dropping it releases the elements with `delete[]`.
+#[repr(C)]
+pub struct UniqueArray<T: ArrayElement> {
+    ptr: unique_ptr_of_array,
+    len: size_t,
+    _marker: PhantomData<T>,
+}
+
+impl<T: ArrayElement> UniqueArray<T> {
+    pub fn new(len: usize) -> Self {
+        let mut array = MaybeUninit::<Self>::uninit();
+        unsafe {
+            T::new_array(array.as_mut_ptr(), len);
+            (*array.as_mut_ptr()).len = len;
+            array.assume_init()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get_ptr(&self) -> *const T {
+        unsafe {
+            cpp!([self as "const std::unique_ptr<uint8_t[]>*"] -> *const c_void as "const void*" {
+                return self->get();
+            }) as *const T
+        }
+    }
+
+    pub fn get_mut_ptr(&mut self) -> *mut T {
+        unsafe {
+            cpp!([self as "std::unique_ptr<uint8_t[]>*"] -> *mut c_void as "void*" {
+                return self->get();
+            }) as *mut T
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        if self.is_empty() {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(self.get_ptr(), self.len()) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.is_empty() {
+            return &mut [];
+        }
+        unsafe { slice::from_raw_parts_mut(self.get_mut_ptr(), self.len()) }
+    }
+}
+
+impl<T: ArrayElement> Drop for UniqueArray<T> {
+    fn drop(&mut self) {
+        unsafe { T::delete_array(self) }
+    }
+}
+
+impl<T: ArrayElement> Deref for UniqueArray<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: ArrayElement> DerefMut for UniqueArray<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> fmt::Debug for UniqueArray<T>
+where
+    T: ArrayElement + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ArrayElement> From<&[T]> for UniqueArray<T> {
+    fn from(values: &[T]) -> Self {
+        let mut array = Self::new(values.len());
+        array.copy_from_slice(values);
+        array
+    }
+}
+
+impl<T: ArrayElement> From<Vec<T>> for UniqueArray<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self::from(values.as_slice())
+    }
+}
+
+#[cfg(all(test, feature = "string"))]
 mod tests {
     use super::*;
+    use crate::mirror::CppMirror;
     use crate::string::String as StlString;
 
+    #[derive(CppMirror)]
+    #[cpp_mirror("class_with_unique_ptr")]
     #[repr(C)]
     struct ClassWithUniquePtr {
         desc: StlString,
@@ -77,4 +359,48 @@ mod tests {
         assert_eq!(x.value.deref(), &23);
         assert_eq!(x.desc.c_str().to_string_lossy(), "hello");
     }
+
+    #[repr(C)]
+    struct StructWithUniqueArray {
+        values: UniqueArray<f32>,
+    }
+
+    #[test]
+    fn unittest_unique_array() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithUniqueArray as "struct_with_unique_array*" {
+                static struct_with_unique_array x{
+                    std::unique_ptr<float[]>(new float[3]{1.0f, 2.0f, 3.0f}), 3
+                };
+                return &x;
+            })
+        };
+
+        assert_eq!(x.values.len(), 3);
+        assert_eq!(x.values.as_slice(), &[1.0f32, 2.0, 3.0]);
+
+        x.values[1] = 5.0;
+        assert_eq!(x.values.as_slice(), &[1.0f32, 5.0, 3.0]);
+
+        let old = std::mem::replace(&mut x.values, UniqueArray::from(vec![7.0f32, 8.0]));
+        assert_eq!(old.as_slice(), &[1.0f32, 5.0, 3.0]);
+        drop(old);
+
+        let x: &StructWithUniqueArray = x;
+        let sum = unsafe {
+            cpp!([x as "const struct_with_unique_array*"] -> f32 as "float" {
+                float sum = 0.0f;
+                for (size_t i = 0; i < x->len; ++i) {
+                    sum += x->values[i];
+                }
+                return sum;
+            })
+        };
+        assert_eq!(sum, 15.0);
+
+        let mut array = UniqueArray::<u8>::new(2);
+        unsafe { u8::delete_array(&mut array) };
+        assert!(array.is_empty());
+        assert_eq!(array.as_slice(), &[] as &[u8]);
+    }
 }
@@ -0,0 +1,38 @@
+//! Build-time checks that Rust structs have the layout of the C++ types they mirror.
+
+pub use cpp_stl_derive::CppMirror;
+
+/// Structs whose layout is checked against a C++ type by `#[derive(CppMirror)]`.
+pub trait CppMirror {
+    /// The name of the C++ type.
+    const CPP_TYPE: &'static str;
+}
+
+/// Whether `path`, as given by `module_path!()`, names `module`, which is relative to the
+/// crate root as recorded by `cpp_stl_build::Mirrors`.
+#[doc(hidden)]
+pub const fn is_module(path: &str, module: &str) -> bool {
+    let path = path.as_bytes();
+    let module = module.as_bytes();
+
+    // Skips the name of the crate and the `::` following it.
+    let mut start = 0;
+    while start < path.len() && path[start] != b':' {
+        start += 1;
+    }
+    if start < path.len() {
+        start += 2;
+    }
+
+    if path.len() - start != module.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < module.len() {
+        if path[start + i] != module[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
@@ -0,0 +1,281 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::marker::PhantomPinned;
+use std::mem::MaybeUninit;
+use std::pin::Pin;
+
+use libc::size_t;
+
+use crate::element::Element;
+use crate::string::String as StlString;
+
+cpp! {{
+    #include <optional>
+    #include <string>
+
+    static_assert(sizeof(std::optional<int64_t>) == 2 * sizeof(int64_t), "");
+    static_assert(alignof(std::optional<int64_t>) == alignof(int64_t), "");
+    static_assert(sizeof(std::optional<std::string>) == sizeof(std::string) + alignof(std::string), "");
+
+    struct struct_with_optionals {
+        std::optional<std::string> nickname;
+        std::optional<int64_t> score;
+    };
+
+    static std::optional<int64_t> parse_score(const std::string& s) {
+        if (s.empty()) {
+            return std::nullopt;
+        }
+        return std::stoll(s);
+    }
+}}
+
+/// Value types of `std::optional<T>` for which the instantiation exists in C++.
+pub trait OptionalValue: Element + Sized {
+    /// Destroys `optional`, including its value if it has one.
+    ///
+    /// # Safety
+    ///
+    /// `optional` must not be used after this call.
+    unsafe fn destruct(optional: &mut CppOptional<Self>);
+
+    /// Constructs the value of `optional` from `value`, destroying the previous one.
+    fn emplace(optional: &mut CppOptional<Self>, value: &Self::Borrowed);
+
+    fn reset(optional: &mut CppOptional<Self>);
+}
+
+/// `std::optional<T>`.
+///
+/// Both libstdc++ and libc++ store the value followed by the engaged flag. Values like
+/// `std::string` may point into themselves, so an optional is pinned like `Map`.
+#[repr(C)]
+pub struct CppOptional<T: OptionalValue> {
+    value: MaybeUninit<T>,
+    engaged: bool,
+    _pinned: PhantomPinned,
+}
+
+impl<T: OptionalValue> CppOptional<T> {
+    /// Creates a disengaged optional.
+    pub fn new() -> Pin<Box<Self>> {
+        Box::pin(CppOptional {
+            value: MaybeUninit::uninit(),
+            engaged: false,
+            _pinned: PhantomPinned,
+        })
+    }
+
+    pub fn is_some(&self) -> bool {
+        self.engaged
+    }
+
+    pub fn is_none(&self) -> bool {
+        !self.engaged
+    }
+
+    pub fn as_ref(&self) -> Option<&T> {
+        if self.engaged {
+            unsafe { Some(&*self.value.as_ptr()) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value for modification; it is not named `as_mut` to avoid `Pin::as_mut`.
+    pub fn as_value_mut(self: Pin<&mut Self>) -> Option<&mut T> {
+        if self.engaged {
+            unsafe { Some(&mut *self.get_unchecked_mut().value.as_mut_ptr()) }
+        } else {
+            None
+        }
+    }
+
+    /// Copies the value into a Rust `Option`.
+    pub fn to_option(&self) -> Option<T::Owned> {
+        self.as_ref().map(T::to_rust)
+    }
+
+    /// Takes the value out, leaving the optional disengaged.
+    pub fn take(self: Pin<&mut Self>) -> Option<T::Owned> {
+        let old = self.to_option();
+        T::reset(unsafe { self.get_unchecked_mut() });
+        old
+    }
+
+    /// Replaces the value with `value`, returning the previous one.
+    pub fn replace(self: Pin<&mut Self>, value: &T::Borrowed) -> Option<T::Owned> {
+        let old = self.to_option();
+        T::emplace(unsafe { self.get_unchecked_mut() }, value);
+        old
+    }
+
+    /// Inserts the value returned by `f` if the optional is disengaged, and returns the
+    /// contained value.
+    pub fn get_or_insert_with<F: FnOnce() -> T::Owned>(mut self: Pin<&mut Self>, f: F) -> &mut T {
+        if !self.engaged {
+            T::emplace(unsafe { self.as_mut().get_unchecked_mut() }, f().borrow());
+        }
+        self.as_value_mut().unwrap()
+    }
+}
+
+impl<T: OptionalValue> Drop for CppOptional<T> {
+    fn drop(&mut self) {
+        if self.engaged {
+            unsafe { T::destruct(self) }
+        }
+    }
+}
+
+impl<T> fmt::Debug for CppOptional<T>
+where
+    T: OptionalValue + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<T: OptionalValue> From<Option<T::Owned>> for Pin<Box<CppOptional<T>>> {
+    fn from(value: Option<T::Owned>) -> Self {
+        let mut optional = CppOptional::new();
+        if let Some(value) = value {
+            optional.as_mut().replace(value.borrow());
+        }
+        optional
+    }
+}
+
+impl OptionalValue for i64 {
+    unsafe fn destruct(optional: &mut CppOptional<Self>) {
+        cpp!([optional as "std::optional<int64_t>*"] {
+            optional->~optional();
+        })
+    }
+
+    fn emplace(optional: &mut CppOptional<Self>, value: &i64) {
+        let value = *value;
+        unsafe {
+            cpp!([optional as "std::optional<int64_t>*", value as "int64_t"] {
+                optional->emplace(value);
+            })
+        }
+    }
+
+    fn reset(optional: &mut CppOptional<Self>) {
+        unsafe {
+            cpp!([optional as "std::optional<int64_t>*"] {
+                optional->reset();
+            })
+        }
+    }
+}
+
+impl OptionalValue for StlString {
+    unsafe fn destruct(optional: &mut CppOptional<Self>) {
+        cpp!([optional as "std::optional<std::string>*"] {
+            optional->~optional();
+        })
+    }
+
+    fn emplace(optional: &mut CppOptional<Self>, value: &str) {
+        let ptr = value.as_ptr();
+        let len = value.len() as size_t;
+        unsafe {
+            cpp!([optional as "std::optional<std::string>*", ptr as "const char*", len as "size_t"] {
+                optional->emplace(ptr, len);
+            })
+        }
+    }
+
+    fn reset(optional: &mut CppOptional<Self>) {
+        unsafe {
+            cpp!([optional as "std::optional<std::string>*"] {
+                optional->reset();
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    struct StructWithOptionals {
+        nickname: CppOptional<StlString>,
+        score: CppOptional<i64>,
+    }
+
+    #[test]
+    fn unittest_optional() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithOptionals as "struct_with_optionals*" {
+                static struct_with_optionals x{"boncheol", std::nullopt};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.nickname.as_ref().unwrap().to_string(), "boncheol");
+        assert!(x.score.is_none());
+        let mut score = unsafe { Pin::new_unchecked(&mut x.score) };
+        assert_eq!(*score.as_mut().get_or_insert_with(|| 10), 10);
+        assert_eq!(*score.as_mut().get_or_insert_with(|| 20), 10);
+        assert_eq!(score.as_mut().replace(&30), Some(10));
+        *score.as_value_mut().unwrap() += 1;
+
+        let mut nickname = unsafe { Pin::new_unchecked(&mut x.nickname) };
+        assert_eq!(
+            nickname.as_mut().replace("bora"),
+            Some("boncheol".to_owned())
+        );
+        assert_eq!(x.nickname.to_option(), Some("bora".to_owned()));
+
+        let (has_nickname, score) = unsafe {
+            let x: &StructWithOptionals = x;
+            let mut has_nickname = false;
+            let score = cpp!([x as "const struct_with_optionals*", mut has_nickname as "bool"]
+                  -> i64 as "int64_t" {
+                has_nickname = x->nickname.has_value() && &*x->nickname == (const void*)&x->nickname;
+                return x->score.value_or(-1);
+            });
+            (has_nickname, score)
+        };
+        assert!(has_nickname);
+        assert_eq!(score, 31);
+
+        let mut nickname = unsafe { Pin::new_unchecked(&mut x.nickname) };
+        assert_eq!(nickname.as_mut().take(), Some("bora".to_owned()));
+        assert_eq!(nickname.as_mut().take(), None);
+        let x: &StructWithOptionals = x;
+        let has_nickname = unsafe {
+            cpp!([x as "const struct_with_optionals*"] -> bool as "bool" {
+                return x->nickname.has_value();
+            })
+        };
+        assert!(!has_nickname);
+    }
+
+    #[test]
+    fn unittest_optional_return() {
+        let parse = |s: &str| {
+            let ptr = s.as_ptr();
+            let len = s.len() as size_t;
+            unsafe {
+                cpp!([ptr as "const char*", len as "size_t"]
+                      -> CppOptional<i64> as "std::optional<int64_t>" {
+                    return parse_score(std::string(ptr, len));
+                })
+            }
+        };
+
+        assert_eq!(parse("42").as_ref(), Some(&42));
+        assert!(parse("").is_none());
+
+        let name: Pin<Box<CppOptional<StlString>>> = Some("junmo".to_owned()).into();
+        assert_eq!(format!("{:?}", name), r#"Some("junmo")"#);
+        let none: Pin<Box<CppOptional<StlString>>> = None.into();
+        assert_eq!(none.to_option(), None);
+    }
+}
@@ -0,0 +1,379 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+
+use libc::size_t;
+
+use crate::bindings::root::rust::*;
+use crate::deque::{Deque, DequeElement};
+use crate::utility::Pair;
+
+cpp! {{
+    #include <deque>
+    #include <queue>
+    #include <utility>
+    #include <vector>
+
+    #include "wrapper.hpp"
+
+    static_assert(sizeof(std::queue<int32_t>) == sizeof(std::deque<int32_t>), "");
+    static_assert(sizeof(std::queue<int64_t>) == sizeof(std::deque<int64_t>), "");
+    static_assert(sizeof(std::queue<void*>) == sizeof(std::deque<void*>), "");
+
+    typedef std::priority_queue<std::pair<int32_t, int64_t>> priority_queue_of_pair_of_int32_t_int64_t;
+
+    static_assert(sizeof(std::priority_queue<int64_t>) == sizeof(rust::priority_queue_of_dummy), "");
+    static_assert(sizeof(priority_queue_of_pair_of_int32_t_int64_t) == sizeof(rust::priority_queue_of_dummy), "");
+
+    struct scheduler {
+        std::priority_queue<std::pair<int32_t, int64_t>> tasks;
+        std::queue<int32_t> pending;
+    };
+}}
+
+/// `std::queue<T>` over its default container `std::deque<T>`, which is its only member.
+#[repr(C)]
+pub struct Queue<T: DequeElement>(Deque<T>);
+
+impl<T: DequeElement> Queue<T> {
+    pub fn new() -> Box<Self> {
+        let queue = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            T::construct(queue as *mut Deque<T>);
+            Box::from_raw(queue)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.0.front()
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.0.get_mut(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.0.back()
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        let index = self.len().checked_sub(1)?;
+        self.0.get_mut(index)
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.0.push_back(value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    /// Pops every element, returning them from the front to the back.
+    pub fn drain(&mut self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.len());
+        while let Some(value) = self.pop() {
+            values.push(value);
+        }
+        values
+    }
+
+    pub fn as_container(&self) -> &Deque<T> {
+        &self.0
+    }
+}
+
+impl<T> fmt::Debug for Queue<T>
+where
+    T: DequeElement + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: DequeElement> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl<T: DequeElement> FromIterator<T> for Box<Queue<T>> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Queue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+/// Element types of `std::priority_queue<T>` for which the instantiation exists in C++.
+///
+/// The instantiations use the default `std::vector<T>` container and `std::less<T>`, so
+/// the greatest element is on top.
+pub trait PriorityQueueElement: Sized {
+    /// Constructs an empty priority queue at `queue`.
+    ///
+    /// # Safety
+    ///
+    /// `queue` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(queue: *mut PriorityQueue<Self>);
+
+    /// # Safety
+    ///
+    /// `queue` must not be used after this call.
+    unsafe fn destruct(queue: &mut PriorityQueue<Self>);
+
+    fn size(queue: &PriorityQueue<Self>) -> usize;
+
+    /// # Safety
+    ///
+    /// `queue` must not be empty.
+    unsafe fn top(queue: &PriorityQueue<Self>) -> *const Self;
+
+    fn push(queue: &mut PriorityQueue<Self>, value: Self);
+
+    /// Removes the top element and returns it.
+    ///
+    /// # Safety
+    ///
+    /// `queue` must not be empty.
+    unsafe fn pop(queue: &mut PriorityQueue<Self>) -> Self;
+}
+
+/// `std::priority_queue<T>`.
+///
+/// The size of the comparator member differs between standard libraries, so the layout
+/// is taken from `std::priority_queue<int32_t>`.
+#[repr(C)]
+pub struct PriorityQueue<T: PriorityQueueElement>(priority_queue_of_dummy, PhantomData<T>);
+
+impl<T: PriorityQueueElement> PriorityQueue<T> {
+    pub fn new() -> Box<Self> {
+        let queue = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            T::construct(queue);
+            Box::from_raw(queue)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        T::size(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn top(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe { Some(&*T::top(self)) }
+    }
+
+    pub fn push(&mut self, value: T) {
+        T::push(self, value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe { Some(T::pop(self)) }
+    }
+
+    /// Pops every element, returning them from the greatest to the least.
+    pub fn drain(&mut self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.len());
+        while let Some(value) = self.pop() {
+            values.push(value);
+        }
+        values
+    }
+}
+
+impl<T: PriorityQueueElement> Drop for PriorityQueue<T> {
+    fn drop(&mut self) {
+        unsafe { T::destruct(self) }
+    }
+}
+
+impl<T: PriorityQueueElement> Extend<T> for PriorityQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T: PriorityQueueElement> FromIterator<T> for Box<PriorityQueue<T>> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = PriorityQueue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl PriorityQueueElement for i64 {
+    unsafe fn construct(queue: *mut PriorityQueue<Self>) {
+        cpp!([queue as "std::priority_queue<int64_t>*"] {
+            new (queue) std::priority_queue<int64_t>();
+        })
+    }
+
+    unsafe fn destruct(queue: &mut PriorityQueue<Self>) {
+        cpp!([queue as "std::priority_queue<int64_t>*"] {
+            queue->~priority_queue();
+        })
+    }
+
+    fn size(queue: &PriorityQueue<Self>) -> usize {
+        unsafe {
+            cpp!([queue as "const std::priority_queue<int64_t>*"] -> size_t as "size_t" {
+                return queue->size();
+            })
+        }
+    }
+
+    unsafe fn top(queue: &PriorityQueue<Self>) -> *const Self {
+        cpp!([queue as "const std::priority_queue<int64_t>*"] -> *const i64 as "const int64_t*" {
+            return &queue->top();
+        })
+    }
+
+    fn push(queue: &mut PriorityQueue<Self>, value: Self) {
+        unsafe {
+            cpp!([queue as "std::priority_queue<int64_t>*", value as "int64_t"] {
+                queue->push(value);
+            })
+        }
+    }
+
+    unsafe fn pop(queue: &mut PriorityQueue<Self>) -> Self {
+        cpp!([queue as "std::priority_queue<int64_t>*"] -> i64 as "int64_t" {
+            int64_t value = queue->top();
+            queue->pop();
+            return value;
+        })
+    }
+}
+
+impl PriorityQueueElement for Pair<i32, i64> {
+    unsafe fn construct(queue: *mut PriorityQueue<Self>) {
+        cpp!([queue as "priority_queue_of_pair_of_int32_t_int64_t*"] {
+            new (queue) priority_queue_of_pair_of_int32_t_int64_t();
+        })
+    }
+
+    unsafe fn destruct(queue: &mut PriorityQueue<Self>) {
+        cpp!([queue as "priority_queue_of_pair_of_int32_t_int64_t*"] {
+            queue->~priority_queue_of_pair_of_int32_t_int64_t();
+        })
+    }
+
+    fn size(queue: &PriorityQueue<Self>) -> usize {
+        unsafe {
+            cpp!([queue as "const priority_queue_of_pair_of_int32_t_int64_t*"] -> size_t as "size_t" {
+                return queue->size();
+            })
+        }
+    }
+
+    unsafe fn top(queue: &PriorityQueue<Self>) -> *const Self {
+        cpp!([queue as "const priority_queue_of_pair_of_int32_t_int64_t*"]
+              -> *const Pair<i32, i64> as "const std::pair<int32_t, int64_t>*" {
+            return &queue->top();
+        })
+    }
+
+    fn push(queue: &mut PriorityQueue<Self>, value: Self) {
+        unsafe {
+            cpp!([queue as "priority_queue_of_pair_of_int32_t_int64_t*", value as "std::pair<int32_t, int64_t>"] {
+                queue->push(value);
+            })
+        }
+    }
+
+    unsafe fn pop(queue: &mut PriorityQueue<Self>) -> Self {
+        cpp!([queue as "priority_queue_of_pair_of_int32_t_int64_t*"]
+              -> Pair<i32, i64> as "std::pair<int32_t, int64_t>" {
+            std::pair<int32_t, int64_t> value = queue->top();
+            queue->pop();
+            return value;
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    struct Scheduler {
+        tasks: PriorityQueue<Pair<i32, i64>>,
+        pending: Queue<i32>,
+    }
+
+    #[test]
+    fn unittest_queue() {
+        let x = unsafe {
+            cpp!([] -> &mut Scheduler as "scheduler*" {
+                static scheduler x;
+                x.tasks.push({1, 100});
+                x.tasks.push({3, 300});
+                x.pending.push(7);
+                return &x;
+            })
+        };
+
+        assert_eq!(x.tasks.len(), 2);
+        assert_eq!(x.tasks.top(), Some(&Pair::new(3, 300)));
+        x.tasks.push(Pair::new(2, 200));
+        x.tasks.push(Pair::new(3, 50));
+        assert_eq!(x.tasks.pop(), Some(Pair::new(3, 300)));
+
+        assert_eq!(x.pending.front(), Some(&7));
+        x.pending.extend(vec![8, 9]);
+        *x.pending.back_mut().unwrap() += 1;
+        assert_eq!(format!("{:?}", x.pending), "[7, 8, 10]");
+
+        let (top, front) = unsafe {
+            let mut front = 0;
+            let top = cpp!([x as "const scheduler*", mut front as "int32_t"] -> i64 as "int64_t" {
+                front = x->pending.front() + x->pending.size();
+                return x->tasks.top().second;
+            });
+            (top, front)
+        };
+        assert_eq!((top, front), (50, 10));
+
+        let tasks: Vec<_> = x
+            .tasks
+            .drain()
+            .into_iter()
+            .map(|task| task.second)
+            .collect();
+        assert_eq!(tasks, [50, 200, 100]);
+        assert!(x.tasks.is_empty());
+        assert_eq!(x.pending.drain(), [7, 8, 10]);
+        assert_eq!(x.pending.pop(), None);
+    }
+
+    #[test]
+    fn unittest_priority_queue() {
+        let mut queue: Box<PriorityQueue<i64>> = vec![5, 1, 8, 3].into_iter().collect();
+        assert_eq!(queue.top(), Some(&8));
+        queue.push(10);
+        assert_eq!(queue.drain(), [10, 8, 5, 3, 1]);
+        assert_eq!(queue.top(), None);
+    }
+}
@@ -0,0 +1,594 @@
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::{PhantomData, PhantomPinned};
+use std::mem::MaybeUninit;
+use std::pin::Pin;
+
+use libc::size_t;
+
+use crate::bindings::root::rust::*;
+use crate::element::Element;
+use crate::string::String as StlString;
+
+cpp! {{
+    #include <algorithm>
+    #include <iterator>
+    #include <set>
+    #include <string>
+
+    #include "wrapper.hpp"
+
+    static_assert(sizeof(std::set<int32_t>) == sizeof(rust::set_of_dummy), "");
+    static_assert(sizeof(std::set<std::string>) == sizeof(rust::set_of_dummy), "");
+    static_assert(sizeof(std::set<int32_t>::const_iterator) == sizeof(rust::set_iterator_of_dummy), "");
+    static_assert(sizeof(std::set<std::string>::const_iterator) == sizeof(rust::set_iterator_of_dummy), "");
+
+    struct struct_with_sets {
+        std::set<int32_t> ids;
+        std::set<std::string> tags;
+    };
+}}
+
+/// Opaque `std::set<T>::const_iterator`.
+#[repr(C)]
+#[derive(Default)]
+pub struct RawIterator(set_iterator_of_dummy);
+
+/// Element types of `std::set<T>` for which the instantiation exists in C++.
+pub trait SetElement: Element + Sized {
+    /// Constructs an empty set at `set`.
+    ///
+    /// # Safety
+    ///
+    /// `set` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(set: *mut Set<Self>);
+
+    /// Destroys `set`.
+    ///
+    /// # Safety
+    ///
+    /// `set` must not be used after this call.
+    unsafe fn destruct(set: &mut Set<Self>);
+
+    fn size(set: &Set<Self>) -> usize;
+
+    fn contains(set: &Set<Self>, value: &Self::Borrowed) -> bool;
+
+    fn insert(set: &mut Set<Self>, value: &Self::Borrowed) -> bool;
+
+    fn erase(set: &mut Set<Self>, value: &Self::Borrowed) -> usize;
+
+    fn clear(set: &mut Set<Self>);
+
+    fn swap(set: &mut Set<Self>, other: &mut Set<Self>);
+
+    fn begin(set: &Set<Self>, it: &mut RawIterator);
+
+    fn end(set: &Set<Self>, it: &mut RawIterator);
+
+    /// Returns the element at `it` and moves `it` to the next element.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an element of a set.
+    unsafe fn next(it: &mut RawIterator) -> *const Self;
+
+    /// Moves `it` to the previous element and returns it.
+    ///
+    /// # Safety
+    ///
+    /// `it` must not point to the first element of a set.
+    unsafe fn prev(it: &mut RawIterator) -> *const Self;
+
+    /// Inserts the elements of `set` or `other` into `out`.
+    fn union(set: &Set<Self>, other: &Set<Self>, out: &mut Set<Self>);
+
+    /// Inserts the elements of both `set` and `other` into `out`.
+    fn intersection(set: &Set<Self>, other: &Set<Self>, out: &mut Set<Self>);
+
+    /// Inserts the elements of `set` but not of `other` into `out`.
+    fn difference(set: &Set<Self>, other: &Set<Self>, out: &mut Set<Self>);
+}
+
+/// `std::set<T>`.
+///
+/// libstdc++ keeps pointers into the set object itself, so it is pinned like `Map`.
+#[repr(C)]
+pub struct Set<T: SetElement>(set_of_dummy, PhantomData<T>, PhantomPinned);
+
+impl<T: SetElement> Set<T> {
+    pub fn new() -> Pin<Box<Self>> {
+        let set = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            T::construct(set);
+            Box::into_pin(Box::from_raw(set))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        T::size(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, value: &T::Borrowed) -> bool {
+        T::contains(self, value)
+    }
+
+    /// Inserts `value`, returning whether it was not present.
+    pub fn insert(self: Pin<&mut Self>, value: &T::Borrowed) -> bool {
+        T::insert(unsafe { self.get_unchecked_mut() }, value)
+    }
+
+    /// Removes `value`, returning whether it was present.
+    pub fn remove(self: Pin<&mut Self>, value: &T::Borrowed) -> bool {
+        T::erase(unsafe { self.get_unchecked_mut() }, value) > 0
+    }
+
+    pub fn clear(self: Pin<&mut Self>) {
+        T::clear(unsafe { self.get_unchecked_mut() });
+    }
+
+    /// Exchanges the contents with `other` without moving either set object.
+    pub fn swap(self: Pin<&mut Self>, other: Pin<&mut Self>) {
+        unsafe { T::swap(self.get_unchecked_mut(), other.get_unchecked_mut()) };
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            set: PhantomData,
+            front: RawIterator::default(),
+            back: RawIterator::default(),
+            len: self.len(),
+        };
+        T::begin(self, &mut iter.front);
+        T::end(self, &mut iter.back);
+        iter
+    }
+
+    pub fn union(&self, other: &Self) -> Pin<Box<Self>> {
+        let mut out = Self::new();
+        T::union(self, other, unsafe { out.as_mut().get_unchecked_mut() });
+        out
+    }
+
+    pub fn intersection(&self, other: &Self) -> Pin<Box<Self>> {
+        let mut out = Self::new();
+        T::intersection(self, other, unsafe { out.as_mut().get_unchecked_mut() });
+        out
+    }
+
+    pub fn difference(&self, other: &Self) -> Pin<Box<Self>> {
+        let mut out = Self::new();
+        T::difference(self, other, unsafe { out.as_mut().get_unchecked_mut() });
+        out
+    }
+}
+
+impl<T: SetElement> Drop for Set<T> {
+    fn drop(&mut self) {
+        unsafe { T::destruct(self) }
+    }
+}
+
+impl<T> fmt::Debug for Set<T>
+where
+    T: SetElement + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: SetElement> IntoIterator for &'a Set<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, Q> Extend<Q> for Pin<&mut Set<T>>
+where
+    T: SetElement,
+    Q: Borrow<T::Borrowed>,
+{
+    fn extend<I: IntoIterator<Item = Q>>(&mut self, iter: I) {
+        for value in iter {
+            self.as_mut().insert(value.borrow());
+        }
+    }
+}
+
+impl<T, Q> FromIterator<Q> for Pin<Box<Set<T>>>
+where
+    T: SetElement,
+    Q: Borrow<T::Borrowed>,
+{
+    fn from_iter<I: IntoIterator<Item = Q>>(iter: I) -> Self {
+        let mut set = Set::new();
+        set.as_mut().extend(iter);
+        set
+    }
+}
+
+impl<T: SetElement> From<BTreeSet<T::Owned>> for Pin<Box<Set<T>>> {
+    fn from(set: BTreeSet<T::Owned>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T> From<&Set<T>> for BTreeSet<T::Owned>
+where
+    T: SetElement,
+    T::Owned: Ord,
+{
+    fn from(set: &Set<T>) -> Self {
+        set.iter().map(T::to_rust).collect()
+    }
+}
+
+pub struct Iter<'a, T: SetElement> {
+    set: PhantomData<&'a Set<T>>,
+    front: RawIterator,
+    back: RawIterator,
+    len: usize,
+}
+
+impl<'a, T: SetElement> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        unsafe { Some(&*T::next(&mut self.front)) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T: SetElement> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        unsafe { Some(&*T::prev(&mut self.back)) }
+    }
+}
+
+impl<'a, T: SetElement> ExactSizeIterator for Iter<'a, T> {}
+
+impl SetElement for i32 {
+    unsafe fn construct(set: *mut Set<Self>) {
+        cpp!([set as "std::set<int32_t>*"] {
+            new (set) std::set<int32_t>();
+        })
+    }
+
+    unsafe fn destruct(set: &mut Set<Self>) {
+        cpp!([set as "std::set<int32_t>*"] {
+            set->~set();
+        })
+    }
+
+    fn size(set: &Set<Self>) -> usize {
+        unsafe {
+            cpp!([set as "const std::set<int32_t>*"] -> size_t as "size_t" {
+                return set->size();
+            })
+        }
+    }
+
+    fn contains(set: &Set<Self>, value: &i32) -> bool {
+        let value = *value;
+        unsafe {
+            cpp!([set as "const std::set<int32_t>*", value as "int32_t"] -> bool as "bool" {
+                return set->count(value) > 0;
+            })
+        }
+    }
+
+    fn insert(set: &mut Set<Self>, value: &i32) -> bool {
+        let value = *value;
+        unsafe {
+            cpp!([set as "std::set<int32_t>*", value as "int32_t"] -> bool as "bool" {
+                return set->insert(value).second;
+            })
+        }
+    }
+
+    fn erase(set: &mut Set<Self>, value: &i32) -> usize {
+        let value = *value;
+        unsafe {
+            cpp!([set as "std::set<int32_t>*", value as "int32_t"] -> size_t as "size_t" {
+                return set->erase(value);
+            })
+        }
+    }
+
+    fn clear(set: &mut Set<Self>) {
+        unsafe {
+            cpp!([set as "std::set<int32_t>*"] {
+                set->clear();
+            })
+        }
+    }
+
+    fn swap(set: &mut Set<Self>, other: &mut Set<Self>) {
+        unsafe {
+            cpp!([set as "std::set<int32_t>*", other as "std::set<int32_t>*"] {
+                set->swap(*other);
+            })
+        }
+    }
+
+    fn begin(set: &Set<Self>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([set as "const std::set<int32_t>*", it as "std::set<int32_t>::const_iterator*"] {
+                *it = set->begin();
+            })
+        }
+    }
+
+    fn end(set: &Set<Self>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([set as "const std::set<int32_t>*", it as "std::set<int32_t>::const_iterator*"] {
+                *it = set->end();
+            })
+        }
+    }
+
+    unsafe fn next(it: &mut RawIterator) -> *const Self {
+        cpp!([it as "std::set<int32_t>::const_iterator*"] -> *const i32 as "const int32_t*" {
+            return &*(*it)++;
+        })
+    }
+
+    unsafe fn prev(it: &mut RawIterator) -> *const Self {
+        cpp!([it as "std::set<int32_t>::const_iterator*"] -> *const i32 as "const int32_t*" {
+            return &*--*it;
+        })
+    }
+
+    fn union(set: &Set<Self>, other: &Set<Self>, out: &mut Set<Self>) {
+        unsafe {
+            cpp!([set as "const std::set<int32_t>*", other as "const std::set<int32_t>*",
+                  out as "std::set<int32_t>*"] {
+                std::set_union(set->begin(), set->end(), other->begin(), other->end(),
+                               std::inserter(*out, out->end()));
+            })
+        }
+    }
+
+    fn intersection(set: &Set<Self>, other: &Set<Self>, out: &mut Set<Self>) {
+        unsafe {
+            cpp!([set as "const std::set<int32_t>*", other as "const std::set<int32_t>*",
+                  out as "std::set<int32_t>*"] {
+                std::set_intersection(set->begin(), set->end(), other->begin(), other->end(),
+                                      std::inserter(*out, out->end()));
+            })
+        }
+    }
+
+    fn difference(set: &Set<Self>, other: &Set<Self>, out: &mut Set<Self>) {
+        unsafe {
+            cpp!([set as "const std::set<int32_t>*", other as "const std::set<int32_t>*",
+                  out as "std::set<int32_t>*"] {
+                std::set_difference(set->begin(), set->end(), other->begin(), other->end(),
+                                    std::inserter(*out, out->end()));
+            })
+        }
+    }
+}
+
+impl SetElement for StlString {
+    unsafe fn construct(set: *mut Set<Self>) {
+        cpp!([set as "std::set<std::string>*"] {
+            new (set) std::set<std::string>();
+        })
+    }
+
+    unsafe fn destruct(set: &mut Set<Self>) {
+        cpp!([set as "std::set<std::string>*"] {
+            set->~set();
+        })
+    }
+
+    fn size(set: &Set<Self>) -> usize {
+        unsafe {
+            cpp!([set as "const std::set<std::string>*"] -> size_t as "size_t" {
+                return set->size();
+            })
+        }
+    }
+
+    fn contains(set: &Set<Self>, value: &str) -> bool {
+        let ptr = value.as_ptr();
+        let len = value.len() as size_t;
+        unsafe {
+            cpp!([set as "const std::set<std::string>*", ptr as "const char*", len as "size_t"]
+                  -> bool as "bool" {
+                return set->count(std::string(ptr, len)) > 0;
+            })
+        }
+    }
+
+    fn insert(set: &mut Set<Self>, value: &str) -> bool {
+        let ptr = value.as_ptr();
+        let len = value.len() as size_t;
+        unsafe {
+            cpp!([set as "std::set<std::string>*", ptr as "const char*", len as "size_t"]
+                  -> bool as "bool" {
+                return set->emplace(ptr, len).second;
+            })
+        }
+    }
+
+    fn erase(set: &mut Set<Self>, value: &str) -> usize {
+        let ptr = value.as_ptr();
+        let len = value.len() as size_t;
+        unsafe {
+            cpp!([set as "std::set<std::string>*", ptr as "const char*", len as "size_t"]
+                  -> size_t as "size_t" {
+                return set->erase(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn clear(set: &mut Set<Self>) {
+        unsafe {
+            cpp!([set as "std::set<std::string>*"] {
+                set->clear();
+            })
+        }
+    }
+
+    fn swap(set: &mut Set<Self>, other: &mut Set<Self>) {
+        unsafe {
+            cpp!([set as "std::set<std::string>*", other as "std::set<std::string>*"] {
+                set->swap(*other);
+            })
+        }
+    }
+
+    fn begin(set: &Set<Self>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([set as "const std::set<std::string>*",
+                  it as "std::set<std::string>::const_iterator*"] {
+                *it = set->begin();
+            })
+        }
+    }
+
+    fn end(set: &Set<Self>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([set as "const std::set<std::string>*",
+                  it as "std::set<std::string>::const_iterator*"] {
+                *it = set->end();
+            })
+        }
+    }
+
+    unsafe fn next(it: &mut RawIterator) -> *const Self {
+        cpp!([it as "std::set<std::string>::const_iterator*"]
+              -> *const StlString as "const std::string*" {
+            return &*(*it)++;
+        })
+    }
+
+    unsafe fn prev(it: &mut RawIterator) -> *const Self {
+        cpp!([it as "std::set<std::string>::const_iterator*"]
+              -> *const StlString as "const std::string*" {
+            return &*--*it;
+        })
+    }
+
+    fn union(set: &Set<Self>, other: &Set<Self>, out: &mut Set<Self>) {
+        unsafe {
+            cpp!([set as "const std::set<std::string>*", other as "const std::set<std::string>*",
+                  out as "std::set<std::string>*"] {
+                std::set_union(set->begin(), set->end(), other->begin(), other->end(),
+                               std::inserter(*out, out->end()));
+            })
+        }
+    }
+
+    fn intersection(set: &Set<Self>, other: &Set<Self>, out: &mut Set<Self>) {
+        unsafe {
+            cpp!([set as "const std::set<std::string>*", other as "const std::set<std::string>*",
+                  out as "std::set<std::string>*"] {
+                std::set_intersection(set->begin(), set->end(), other->begin(), other->end(),
+                                      std::inserter(*out, out->end()));
+            })
+        }
+    }
+
+    fn difference(set: &Set<Self>, other: &Set<Self>, out: &mut Set<Self>) {
+        unsafe {
+            cpp!([set as "const std::set<std::string>*", other as "const std::set<std::string>*",
+                  out as "std::set<std::string>*"] {
+                std::set_difference(set->begin(), set->end(), other->begin(), other->end(),
+                                    std::inserter(*out, out->end()));
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    struct StructWithSets {
+        ids: Set<i32>,
+        tags: Set<StlString>,
+    }
+
+    #[test]
+    fn unittest_set() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithSets as "struct_with_sets*" {
+                static struct_with_sets x{{30, 10, 20}, {"red", "blue"}};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.ids.len(), 3);
+        assert!(x.ids.contains(&10));
+        assert!(!x.ids.contains(&11));
+        assert_eq!(x.ids.iter().copied().collect::<Vec<_>>(), vec![10, 20, 30]);
+        assert_eq!(
+            x.ids.iter().rev().copied().collect::<Vec<_>>(),
+            vec![30, 20, 10]
+        );
+
+        let mut ids = unsafe { Pin::new_unchecked(&mut x.ids) };
+        assert!(ids.as_mut().insert(&15));
+        assert!(!ids.as_mut().insert(&15));
+        assert!(ids.as_mut().remove(&30));
+        assert!(!ids.as_mut().remove(&30));
+        assert_eq!(
+            BTreeSet::from(&x.ids),
+            vec![10, 15, 20].into_iter().collect()
+        );
+
+        unsafe { Pin::new_unchecked(&mut x.tags) }.extend(vec!["green", "blue"]);
+        assert_eq!(format!("{:?}", x.tags), r#"{"blue", "green", "red"}"#);
+
+        let x: &StructWithSets = x;
+        let count = unsafe {
+            cpp!([x as "const struct_with_sets*"] -> size_t as "size_t" {
+                return x->ids.count(15) + x->tags.count("green");
+            })
+        };
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn unittest_set_algebra() {
+        let a: Pin<Box<Set<StlString>>> = vec!["a", "b", "c"].into_iter().collect();
+        let b: Pin<Box<Set<StlString>>> = vec!["b", "c", "d"].into_iter().collect();
+
+        let to_vec = |set: &Set<StlString>| set.iter().map(|s| s.to_rust()).collect::<Vec<_>>();
+        assert_eq!(to_vec(&a.union(&b)), vec!["a", "b", "c", "d"]);
+        assert_eq!(to_vec(&a.intersection(&b)), vec!["b", "c"]);
+        assert_eq!(to_vec(&a.difference(&b)), vec!["a"]);
+
+        let ids: BTreeSet<i32> = vec![3, 1, 2].into_iter().collect();
+        let set: Pin<Box<Set<i32>>> = ids.clone().into();
+        assert_eq!(BTreeSet::from(&*set), ids);
+        assert_eq!(set.difference(&set).len(), 0);
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+
+use crate::vector::{VectorSlice, VectorSliceMut};
+
+cpp! {{
+    #include <numeric>
+    #include <span>
+
+    #include "wrapper.hpp"
+
+    static_assert(sizeof(std::span<const float>) == sizeof(rust::span_of_float), "");
+    static_assert(sizeof(std::span<int32_t>) == sizeof(rust::span_of_float), "");
+    static_assert(alignof(std::span<const float>) == alignof(rust::span_of_float), "");
+
+    static float sum(std::span<const float> values) {
+        return std::accumulate(values.begin(), values.end(), 0.0f);
+    }
+
+    static void scale(std::span<float> values, float factor) {
+        for (float& value : values) {
+            value *= factor;
+        }
+    }
+}}
+
+/// `std::span<const T>` with a dynamic extent, which borrows the elements of a Rust
+/// slice or a C++ vector without copying them.
+///
+/// Both libstdc++ and libc++ lay it out as the data pointer followed by the size.
+#[repr(C)]
+pub struct Span<'a, T> {
+    ptr: *const T,
+    len: usize,
+    marker: PhantomData<&'a [T]>,
+}
+
+impl<'a, T> Span<'a, T> {
+    pub fn new(values: &'a [T]) -> Self {
+        Span {
+            ptr: values.as_ptr(),
+            len: values.len(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_slice(&self) -> &'a [T] {
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl<'a, T> Clone for Span<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for Span<'a, T> {}
+
+impl<'a, T> Default for Span<'a, T> {
+    fn default() -> Self {
+        Span::new(&[])
+    }
+}
+
+impl<'a, T> Deref for Span<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Span<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<'a, T> From<&'a [T]> for Span<'a, T> {
+    fn from(values: &'a [T]) -> Self {
+        Span::new(values)
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a [T; N]> for Span<'a, T> {
+    fn from(values: &'a [T; N]) -> Self {
+        Span::new(values)
+    }
+}
+
+impl<'a, V: VectorSlice> From<&'a V> for Span<'a, V::Item> {
+    fn from(vector: &'a V) -> Self {
+        Span::new(vector.as_slice())
+    }
+}
+
+/// `std::span<T>` with a dynamic extent, which mutably borrows the elements of a Rust
+/// slice or a C++ vector.
+#[repr(C)]
+pub struct SpanMut<'a, T> {
+    ptr: *mut T,
+    len: usize,
+    marker: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T> SpanMut<'a, T> {
+    pub fn new(values: &'a mut [T]) -> Self {
+        SpanMut {
+            ptr: values.as_mut_ptr(),
+            len: values.len(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_span(&self) -> Span<'_, T> {
+        Span::new(self)
+    }
+
+    pub fn into_slice(self) -> &'a mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl<'a, T> Deref for SpanMut<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl<'a, T> DerefMut for SpanMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for SpanMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T> From<&'a mut [T]> for SpanMut<'a, T> {
+    fn from(values: &'a mut [T]) -> Self {
+        SpanMut::new(values)
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a mut [T; N]> for SpanMut<'a, T> {
+    fn from(values: &'a mut [T; N]) -> Self {
+        SpanMut::new(values)
+    }
+}
+
+impl<'a, V: VectorSliceMut> From<&'a mut V> for SpanMut<'a, V::Item> {
+    fn from(vector: &'a mut V) -> Self {
+        SpanMut::new(vector.as_mut_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::VectorOfF32;
+
+    fn sum(values: Span<'_, f32>) -> f32 {
+        unsafe {
+            cpp!([values as "std::span<const float>"] -> f32 as "float" {
+                return sum(values);
+            })
+        }
+    }
+
+    fn scale(values: SpanMut<'_, f32>, factor: f32) {
+        unsafe {
+            cpp!([values as "std::span<float>", factor as "float"] {
+                scale(values, factor);
+            })
+        }
+    }
+
+    #[test]
+    fn unittest_span() {
+        let mut values = vec![1.0, 2.0, 3.5];
+        assert_eq!(sum(values.as_slice().into()), 6.5);
+        assert_eq!(sum(Span::new(&values[1..])), 5.5);
+        assert_eq!(sum(Span::default()), 0.0);
+
+        scale(SpanMut::new(&mut values), 2.0);
+        assert_eq!(values, [2.0, 4.0, 7.0]);
+
+        let mut array = [1.0f32; 4];
+        scale((&mut array).into(), 0.5);
+        assert_eq!(sum((&array).into()), 2.0);
+
+        let vector = unsafe {
+            cpp!([] -> &mut VectorOfF32 as "std::vector<float>*" {
+                static std::vector<float> x{0.5f, 1.5f};
+                return &x;
+            })
+        };
+        scale(vector.into(), 4.0);
+        let span = Span::from(&*vector);
+        assert_eq!(span.len(), 2);
+        assert_eq!(format!("{:?}", span), "[2.0, 6.0]");
+        assert_eq!(sum(span), 8.0);
+
+        let mut span = SpanMut::new(&mut values);
+        span[0] = 0.0;
+        assert_eq!(sum(span.as_span()), 11.0);
+        assert_eq!(span.into_slice(), &[0.0, 4.0, 7.0]);
+    }
+}
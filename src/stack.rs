@@ -0,0 +1,158 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem::MaybeUninit;
+
+use crate::deque::{Deque, DequeElement};
+
+cpp! {{
+    #include <deque>
+    #include <stack>
+
+    static_assert(sizeof(std::stack<int32_t>) == sizeof(std::deque<int32_t>), "");
+    static_assert(sizeof(std::stack<int64_t>) == sizeof(std::deque<int64_t>), "");
+    static_assert(sizeof(std::stack<void*>) == sizeof(std::deque<void*>), "");
+
+    struct struct_with_stack {
+        std::stack<int64_t> undo;
+    };
+}}
+
+/// `std::stack<T>` over its default container `std::deque<T>`, which is its only member.
+#[repr(C)]
+pub struct Stack<T: DequeElement>(Deque<T>);
+
+impl<T: DequeElement> Stack<T> {
+    pub fn new() -> Box<Self> {
+        let stack = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            T::construct(stack as *mut Deque<T>);
+            Box::from_raw(stack)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn top(&self) -> Option<&T> {
+        self.0.back()
+    }
+
+    pub fn top_mut(&mut self) -> Option<&mut T> {
+        let index = self.len().checked_sub(1)?;
+        self.0.get_mut(index)
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.0.push_back(value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop_back()
+    }
+
+    /// Pops every element, returning them from the top down.
+    pub fn drain(&mut self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.len());
+        while let Some(value) = self.pop() {
+            values.push(value);
+        }
+        values
+    }
+
+    /// Returns the underlying container, whose back is the top of the stack.
+    pub fn as_container(&self) -> &Deque<T> {
+        &self.0
+    }
+}
+
+impl<T> fmt::Debug for Stack<T>
+where
+    T: DequeElement + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: DequeElement> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl<T: DequeElement> FromIterator<T> for Box<Stack<T>> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Stack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::UniquePtr;
+
+    #[repr(C)]
+    struct StructWithStack {
+        undo: Stack<i64>,
+    }
+
+    #[test]
+    fn unittest_stack() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithStack as "struct_with_stack*" {
+                static struct_with_stack x;
+                x.undo.push(1);
+                x.undo.push(2);
+                return &x;
+            })
+        };
+
+        assert_eq!(x.undo.len(), 2);
+        assert_eq!(x.undo.top(), Some(&2));
+        *x.undo.top_mut().unwrap() = 3;
+        x.undo.push(4);
+        assert_eq!(format!("{:?}", x.undo), "[1, 3, 4]");
+        assert_eq!(x.undo.pop(), Some(4));
+
+        let top = unsafe {
+            cpp!([x as "const struct_with_stack*"] -> i64 as "int64_t" {
+                return x->undo.top() * 10 + x->undo.size();
+            })
+        };
+        assert_eq!(top, 32);
+
+        assert_eq!(x.undo.drain(), [3, 1]);
+        assert!(x.undo.is_empty());
+        assert_eq!(x.undo.pop(), None);
+    }
+
+    #[test]
+    fn unittest_stack_of_unique_ptr() {
+        let mut stack: Box<Stack<UniquePtr<i32>>> = (0..3)
+            .map(|i| unsafe {
+                let raw = cpp!([i as "int32_t"] -> *mut i32 as "int32_t*" {
+                    return new int32_t(i);
+                });
+                UniquePtr::from_raw(raw)
+            })
+            .collect();
+        assert_eq!(**stack.top().unwrap(), 2);
+        let values: Vec<_> = stack
+            .drain()
+            .into_iter()
+            .map(|mut ptr| {
+                let value = *ptr;
+                ptr.reset();
+                value
+            })
+            .collect();
+        assert_eq!(values, [2, 1, 0]);
+    }
+}
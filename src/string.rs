@@ -1,10 +1,12 @@
 use std::ffi::CStr;
 use std::fmt;
 use std::os::raw::c_char;
+use std::slice;
 
 use libc::size_t;
 
 use crate::bindings::root::std::string;
+use crate::element::Element;
 
 cpp! {{
     #include <string>
@@ -40,6 +42,10 @@ impl String {
         }
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     pub fn c_str(&self) -> &CStr {
         unsafe {
             CStr::from_ptr(cpp!([self as "const std::string*"]
@@ -49,6 +55,15 @@ impl String {
         }
     }
 
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            let ptr = cpp!([self as "const std::string*"] -> *const u8 as "const char*" {
+                return self->data();
+            });
+            slice::from_raw_parts(ptr, self.len())
+        }
+    }
+
     pub fn assign<S: AsRef<CStr>>(&mut self, s: S) {
         let s = s.as_ref();
         let ptr = s.as_ptr();
@@ -58,6 +73,29 @@ impl String {
             })
         }
     }
+
+    pub fn assign_str(&mut self, s: &str) {
+        let ptr = s.as_ptr();
+        let len = s.len() as size_t;
+        unsafe {
+            cpp!([self as "std::string*", ptr as "const char*", len as "size_t"] {
+                self->assign(ptr, len);
+            })
+        }
+    }
+}
+
+impl Element for String {
+    type Borrowed = str;
+    type Owned = std::string::String;
+
+    fn to_rust(&self) -> Self::Owned {
+        std::string::String::from_utf8_lossy(self.as_bytes()).into_owned()
+    }
+
+    fn assign(&mut self, value: &Self::Borrowed) {
+        self.assign_str(value);
+    }
 }
 
 #[cfg(test)]
@@ -86,5 +124,10 @@ mod tests {
 
         x.first_name.assign(CString::new("junmo").unwrap());
         assert_eq!(x.first_name.c_str().to_string_lossy(), "junmo");
+
+        x.last_name.assign_str("park\0lee");
+        assert_eq!(x.last_name.len(), 8);
+        assert_eq!(x.last_name.as_bytes(), b"park\0lee");
+        assert_eq!(x.last_name.to_rust(), "park\0lee");
     }
 }
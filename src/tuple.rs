@@ -0,0 +1,288 @@
+use crate::element::Element;
+
+cpp! {{
+    #include <string>
+    #include <tuple>
+
+    struct struct_with_tuples {
+        std::tuple<int, std::string, double> record;
+        std::tuple<int8_t, int64_t, int16_t, float, uint8_t, double> mixed;
+        std::tuple<int8_t, double> pair;
+        std::tuple<int16_t, int8_t, int32_t, int64_t> quad;
+        std::tuple<uint8_t, int32_t, int8_t, double, int16_t> quint;
+    };
+
+    // The fields of `Tuple*` are ordered for the standard library `build.rs` detects.
+    #if defined(_LIBCPP_VERSION) != defined(CPP_STL_LIBCXX)
+    #error "the standard library differs from the one build.rs detected"
+    #endif
+
+    static size_t offset_of(const void* tuple, const void* element) {
+        return static_cast<const char*>(element) - static_cast<const char*>(tuple);
+    }
+}}
+
+cpp! {{
+    #include <type_traits>
+    #include <vector>
+
+    // Stores the offset of each element of `tuple` into `offsets`.
+    template <size_t I = 0, typename... T>
+    static typename std::enable_if<I == sizeof...(T)>::type
+    element_offsets(const std::tuple<T...>&, size_t*) {}
+
+    template <size_t I = 0, typename... T>
+    static typename std::enable_if<I < sizeof...(T)>::type
+    element_offsets(const std::tuple<T...>& tuple, size_t* offsets) {
+        offsets[I] = offset_of(&tuple, &std::get<I>(tuple));
+        element_offsets<I + 1>(tuple, offsets);
+    }
+
+    struct struct_with_tuple_vectors {
+        std::vector<std::tuple<int32_t, float, double>> samples;
+    };
+}}
+
+// libstdc++ derives `std::tuple<A, B, ...>` from `std::tuple<B, ...>` and appends `A`
+// after its base, so the elements are laid out in reverse order, while libc++ lays them
+// out in element order. The fields below are declared in memory order, and the last list
+// of each invocation gives them in element order.
+macro_rules! add_tuple {
+    ($($name:ident<$($t:ident),*> { $($field:ident: $ft:ident),* } ($($element:ident),*))*) => ($(
+        /// `std::tuple` mirror whose fields are laid out as the standard library does.
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $name<$($t),*> {
+            $($field: $ft),*
+        }
+
+        impl<$($t),*> $name<$($t),*> {
+            pub fn new($($element: $t),*) -> Self {
+                $name { $($element),* }
+            }
+
+            /// Returns references to the elements in element order.
+            pub fn as_refs(&self) -> ($(&$t,)*) {
+                ($(&self.$element,)*)
+            }
+
+            /// Returns mutable references to the elements in element order.
+            pub fn as_muts(&mut self) -> ($(&mut $t,)*) {
+                ($(&mut self.$element,)*)
+            }
+        }
+
+        impl<$($t),*> From<($($t,)*)> for $name<$($t),*> {
+            fn from(($($element,)*): ($($t,)*)) -> Self {
+                $name { $($element),* }
+            }
+        }
+
+        impl<$($t),*> From<$name<$($t),*>> for ($($t,)*) {
+            fn from(tuple: $name<$($t),*>) -> Self {
+                ($(tuple.$element,)*)
+            }
+        }
+
+        impl<$($t: Copy),*> Element for $name<$($t),*> {
+            type Borrowed = Self;
+            type Owned = Self;
+
+            fn to_rust(&self) -> Self::Owned {
+                *self
+            }
+
+            fn assign(&mut self, value: &Self::Borrowed) {
+                *self = *value;
+            }
+        }
+    )*)
+}
+
+#[cfg(not(cpp_stl_libcxx))]
+add_tuple! {
+    Tuple2<A, B> { b: B, a: A } (a, b)
+    Tuple3<A, B, C> { c: C, b: B, a: A } (a, b, c)
+    Tuple4<A, B, C, D> { d: D, c: C, b: B, a: A } (a, b, c, d)
+    Tuple5<A, B, C, D, E> { e: E, d: D, c: C, b: B, a: A } (a, b, c, d, e)
+    Tuple6<A, B, C, D, E, F> { f: F, e: E, d: D, c: C, b: B, a: A } (a, b, c, d, e, f)
+}
+
+#[cfg(cpp_stl_libcxx)]
+add_tuple! {
+    Tuple2<A, B> { a: A, b: B } (a, b)
+    Tuple3<A, B, C> { a: A, b: B, c: C } (a, b, c)
+    Tuple4<A, B, C, D> { a: A, b: B, c: C, d: D } (a, b, c, d)
+    Tuple5<A, B, C, D, E> { a: A, b: B, c: C, d: D, e: E } (a, b, c, d, e)
+    Tuple6<A, B, C, D, E, F> { a: A, b: B, c: C, d: D, e: E, f: F } (a, b, c, d, e, f)
+}
+
+#[cfg(all(test, feature = "string"))]
+mod tests {
+    use super::*;
+    use crate::string::String as StlString;
+    #[cfg(feature = "vector")]
+    use crate::vector::{VectorInsert, VectorOfTupleI32F32F64, VectorRemove};
+
+    #[repr(C)]
+    struct StructWithTuples {
+        record: Tuple3<i32, StlString, f64>,
+        mixed: Tuple6<i8, i64, i16, f32, u8, f64>,
+        pair: Tuple2<i8, f64>,
+        quad: Tuple4<i16, i8, i32, i64>,
+        quint: Tuple5<u8, i32, i8, f64, i16>,
+    }
+
+    fn offset<T, U>(base: &T, field: &U) -> usize {
+        field as *const U as usize - base as *const T as usize
+    }
+
+    #[test]
+    fn unittest_tuple() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithTuples as "struct_with_tuples*" {
+                static struct_with_tuples x{
+                    std::make_tuple(7, "seoul", 0.25), std::make_tuple(-1, 1LL << 40, 300, 1.5f, 255, 2.5),
+                    std::make_tuple(3, 0.5), std::make_tuple(1, 2, 3, 4), std::make_tuple(5, 6, 7, 8.0, 9)
+                };
+                return &x;
+            })
+        };
+
+        let (id, city, ratio) = x.record.as_refs();
+        assert_eq!(
+            (*id, city.to_string(), *ratio),
+            (7, "seoul".to_owned(), 0.25)
+        );
+        assert_eq!(
+            <(i8, i64, i16, f32, u8, f64)>::from(x.mixed),
+            (-1, 1 << 40, 300, 1.5, 255, 2.5)
+        );
+
+        *x.record.as_muts().0 = 8;
+        x.record.as_muts().1.assign_str("busan");
+        x.mixed = (1, 2, 3, 4.0, 5, 6.0).into();
+
+        let x: &StructWithTuples = x;
+        let sum = unsafe {
+            cpp!([x as "const struct_with_tuples*"] -> f64 as "double" {
+                return std::get<0>(x->record) + std::get<1>(x->record).size() + std::get<0>(x->mixed)
+                    + std::get<1>(x->mixed) + std::get<2>(x->mixed) + std::get<3>(x->mixed)
+                    + std::get<4>(x->mixed) + std::get<5>(x->mixed);
+            })
+        };
+        assert_eq!(sum, 8.0 + 5.0 + 21.0);
+        assert_eq!(<(i8, f64)>::from(x.pair), (3, 0.5));
+        assert_eq!(<(i16, i8, i32, i64)>::from(x.quad), (1, 2, 3, 4));
+        assert_eq!(<(u8, i32, i8, f64, i16)>::from(x.quint), (5, 6, 7, 8.0, 9));
+
+        let mut offsets = [0usize; 20];
+        unsafe {
+            let offsets = offsets.as_mut_ptr();
+            cpp!([x as "const struct_with_tuples*", offsets as "size_t*"] {
+                element_offsets(x->record, offsets);
+                element_offsets(x->mixed, offsets + 3);
+                element_offsets(x->pair, offsets + 9);
+                element_offsets(x->quad, offsets + 11);
+                element_offsets(x->quint, offsets + 15);
+            })
+        };
+
+        let record = x.record.as_refs();
+        let mixed = x.mixed.as_refs();
+        let pair = x.pair.as_refs();
+        let quad = x.quad.as_refs();
+        let quint = x.quint.as_refs();
+        assert_eq!(
+            offsets,
+            [
+                offset(&x.record, record.0),
+                offset(&x.record, record.1),
+                offset(&x.record, record.2),
+                offset(&x.mixed, mixed.0),
+                offset(&x.mixed, mixed.1),
+                offset(&x.mixed, mixed.2),
+                offset(&x.mixed, mixed.3),
+                offset(&x.mixed, mixed.4),
+                offset(&x.mixed, mixed.5),
+                offset(&x.pair, pair.0),
+                offset(&x.pair, pair.1),
+                offset(&x.quad, quad.0),
+                offset(&x.quad, quad.1),
+                offset(&x.quad, quad.2),
+                offset(&x.quad, quad.3),
+                offset(&x.quint, quint.0),
+                offset(&x.quint, quint.1),
+                offset(&x.quint, quint.2),
+                offset(&x.quint, quint.3),
+                offset(&x.quint, quint.4),
+            ]
+        );
+    }
+
+    #[cfg(feature = "vector")]
+    #[repr(C)]
+    struct StructWithTupleVectors {
+        samples: VectorOfTupleI32F32F64,
+    }
+
+    #[cfg(feature = "vector")]
+    #[test]
+    fn unittest_vector_of_tuple() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithTupleVectors as "struct_with_tuple_vectors*" {
+                static struct_with_tuple_vectors x{{std::make_tuple(1, 0.5f, 0.25), std::make_tuple(2, 1.5f, 0.75)}};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.samples.len(), 2);
+        assert_eq!(<(i32, f32, f64)>::from(x.samples[1]), (2, 1.5, 0.75));
+
+        x.samples.push_back((3, 2.5, 1.25).into());
+        *x.samples[0].as_muts().1 = 4.5;
+        x.samples.erase(1);
+
+        let x: &StructWithTupleVectors = x;
+        let (len, sum) = unsafe {
+            let mut len = 0usize;
+            let sum = cpp!([x as "const struct_with_tuple_vectors*", mut len as "size_t"] -> f64 as "double" {
+                len = x->samples.size();
+                double sum = 0;
+                for (const auto& sample : x->samples) {
+                    sum += std::get<0>(sample) + std::get<1>(sample) + std::get<2>(sample);
+                }
+                return sum;
+            });
+            (len, sum)
+        };
+        assert_eq!(len, 2);
+        assert_eq!(sum, 1.0 + 4.5 + 0.25 + 3.0 + 2.5 + 1.25);
+
+        let element = &x.samples[0];
+        let (a, b, c) = element.as_refs();
+        let mut offsets = [0usize; 3];
+        unsafe {
+            let offsets = offsets.as_mut_ptr();
+            cpp!([x as "const struct_with_tuple_vectors*", offsets as "size_t*"] {
+                element_offsets(x->samples[0], offsets);
+            })
+        };
+        assert_eq!(
+            offsets,
+            [offset(element, a), offset(element, b), offset(element, c)]
+        );
+    }
+
+    #[test]
+    fn unittest_tuple_conversion() {
+        let tuple = Tuple2::new(1u8, 2.0f32);
+        assert_eq!(tuple.as_refs(), (&1, &2.0));
+        assert_eq!(Tuple2::from((1u8, 2.0f32)), tuple);
+
+        let mut tuple: Tuple4<_, _, _, _> = (1, 2, 3, 4).into();
+        *tuple.as_muts().3 = 5;
+        assert_eq!(<(i32, i32, i32, i32)>::from(tuple), (1, 2, 3, 5));
+        assert_eq!(Tuple5::new(1, 2, 3, 4, 5).as_refs().4, &5);
+    }
+}
@@ -0,0 +1,1010 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::marker::{PhantomData, PhantomPinned};
+use std::mem::MaybeUninit;
+use std::pin::Pin;
+use std::ptr;
+
+use libc::size_t;
+
+use crate::bindings::root::rust::*;
+use crate::element::Element;
+use crate::string::String as StlString;
+
+cpp! {{
+    #include <string>
+    #include <unordered_map>
+
+    #include "wrapper.hpp"
+
+    static_assert(
+        sizeof(std::unordered_map<std::string, int64_t>) == sizeof(rust::unordered_map_of_dummy), "");
+    static_assert(
+        sizeof(std::unordered_map<int64_t, std::string>) == sizeof(rust::unordered_map_of_dummy), "");
+    static_assert(
+        sizeof(std::unordered_map<std::string, int64_t>::const_iterator)
+            == sizeof(rust::unordered_map_iterator_of_dummy), "");
+    static_assert(
+        sizeof(std::unordered_map<int64_t, std::string>::const_iterator)
+            == sizeof(rust::unordered_map_iterator_of_dummy), "");
+
+    static_assert(
+        sizeof(std::unordered_multimap<std::string, int64_t>) == sizeof(rust::unordered_map_of_dummy), "");
+    static_assert(
+        sizeof(std::unordered_multimap<std::string, int64_t>::const_iterator)
+            == sizeof(rust::unordered_map_iterator_of_dummy), "");
+
+    struct struct_with_unordered_maps {
+        std::unordered_map<std::string, int64_t> counts;
+        std::unordered_map<int64_t, std::string> labels;
+    };
+
+    struct struct_with_unordered_multimaps {
+        std::unordered_multimap<std::string, int64_t> tags;
+    };
+}}
+
+/// Opaque `std::unordered_map<K, V>::const_iterator`, which is also used for
+/// `std::unordered_multimap<K, V>`.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct RawIterator(unordered_map_iterator_of_dummy);
+
+/// Key types of `std::unordered_map<K, V>` for which the instantiation exists in C++.
+///
+/// Keys are always hashed and compared in C++, so both languages find the same entries.
+pub trait UnorderedMapKey<V: Element>: Element + Sized {
+    /// Constructs an empty map at `map`.
+    ///
+    /// # Safety
+    ///
+    /// `map` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(map: *mut UnorderedMap<Self, V>);
+
+    /// Destroys `map`.
+    ///
+    /// # Safety
+    ///
+    /// `map` must not be used after this call.
+    unsafe fn destruct(map: &mut UnorderedMap<Self, V>);
+
+    fn size(map: &UnorderedMap<Self, V>) -> usize;
+
+    /// Returns the value of `key`, or null if there is none.
+    fn find(map: &UnorderedMap<Self, V>, key: &Self::Borrowed) -> *const V;
+
+    /// Returns the value of `key`, inserting a default-constructed one if there is none.
+    fn entry(map: &mut UnorderedMap<Self, V>, key: &Self::Borrowed) -> *mut V;
+
+    fn erase(map: &mut UnorderedMap<Self, V>, key: &Self::Borrowed) -> usize;
+
+    fn clear(map: &mut UnorderedMap<Self, V>);
+
+    fn swap(map: &mut UnorderedMap<Self, V>, other: &mut UnorderedMap<Self, V>);
+
+    fn reserve(map: &mut UnorderedMap<Self, V>, count: usize);
+
+    fn load_factor(map: &UnorderedMap<Self, V>) -> f32;
+
+    fn bucket_count(map: &UnorderedMap<Self, V>) -> usize;
+
+    fn begin(map: &UnorderedMap<Self, V>, it: &mut RawIterator);
+
+    /// Returns the entry at `it` and moves `it` to the next entry.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an entry of a map.
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const V);
+}
+
+/// `std::unordered_map<K, V>`.
+///
+/// libstdc++ keeps a pointer into the map object itself while it has a single bucket, so
+/// it is pinned like `Map`.
+#[repr(C)]
+pub struct UnorderedMap<K: UnorderedMapKey<V>, V: Element>(
+    unordered_map_of_dummy,
+    PhantomData<(K, V)>,
+    PhantomPinned,
+);
+
+impl<K: UnorderedMapKey<V>, V: Element> UnorderedMap<K, V> {
+    pub fn new() -> Pin<Box<Self>> {
+        let map = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            K::construct(map);
+            Box::into_pin(Box::from_raw(map))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        K::size(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, key: &K::Borrowed) -> Option<&V> {
+        unsafe { K::find(self, key).as_ref() }
+    }
+
+    /// Returns the value of `key` for modification; it is not named `get_mut` to avoid
+    /// `Pin::get_mut`.
+    pub fn get_value_mut(self: Pin<&mut Self>, key: &K::Borrowed) -> Option<&mut V> {
+        unsafe { (K::find(&self, key) as *mut V).as_mut() }
+    }
+
+    pub fn contains_key(&self, key: &K::Borrowed) -> bool {
+        !K::find(self, key).is_null()
+    }
+
+    /// Returns the value of `key`, inserting a default-constructed one first if there is
+    /// none, like `operator[]`.
+    pub fn entry(self: Pin<&mut Self>, key: &K::Borrowed) -> &mut V {
+        unsafe { &mut *K::entry(self.get_unchecked_mut(), key) }
+    }
+
+    /// Inserts `value` for `key`, returning the previous value if there was one.
+    pub fn insert(
+        self: Pin<&mut Self>,
+        key: &K::Borrowed,
+        value: &V::Borrowed,
+    ) -> Option<V::Owned> {
+        let old = self.get(key).map(V::to_rust);
+        self.entry(key).assign(value);
+        old
+    }
+
+    pub fn remove(self: Pin<&mut Self>, key: &K::Borrowed) -> Option<V::Owned> {
+        let old = self.get(key).map(V::to_rust);
+        if old.is_some() {
+            K::erase(unsafe { self.get_unchecked_mut() }, key);
+        }
+        old
+    }
+
+    pub fn clear(self: Pin<&mut Self>) {
+        K::clear(unsafe { self.get_unchecked_mut() });
+    }
+
+    /// Exchanges the contents with `other` without moving either map object.
+    pub fn swap(self: Pin<&mut Self>, other: Pin<&mut Self>) {
+        unsafe { K::swap(self.get_unchecked_mut(), other.get_unchecked_mut()) };
+    }
+
+    /// Rehashes the map so that it can hold at least `count` entries without rehashing.
+    pub fn reserve(self: Pin<&mut Self>, count: usize) {
+        K::reserve(unsafe { self.get_unchecked_mut() }, count);
+    }
+
+    pub fn load_factor(&self) -> f32 {
+        K::load_factor(self)
+    }
+
+    pub fn bucket_count(&self) -> usize {
+        K::bucket_count(self)
+    }
+
+    /// Returns the entries in the order C++ iterates them, which groups them by bucket.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter {
+            map: PhantomData,
+            it: RawIterator::default(),
+            len: self.len(),
+        };
+        K::begin(self, &mut iter.it);
+        iter
+    }
+}
+
+impl<K: UnorderedMapKey<V>, V: Element> Drop for UnorderedMap<K, V> {
+    fn drop(&mut self) {
+        unsafe { K::destruct(self) }
+    }
+}
+
+impl<K, V> fmt::Debug for UnorderedMap<K, V>
+where
+    K: UnorderedMapKey<V> + fmt::Debug,
+    V: Element + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K: UnorderedMapKey<V>, V: Element> IntoIterator for &'a UnorderedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, Q, R> Extend<(Q, R)> for Pin<&mut UnorderedMap<K, V>>
+where
+    K: UnorderedMapKey<V>,
+    V: Element,
+    Q: Borrow<K::Borrowed>,
+    R: Borrow<V::Borrowed>,
+{
+    fn extend<I: IntoIterator<Item = (Q, R)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.as_mut().insert(key.borrow(), value.borrow());
+        }
+    }
+}
+
+impl<K, V, Q, R> FromIterator<(Q, R)> for Pin<Box<UnorderedMap<K, V>>>
+where
+    K: UnorderedMapKey<V>,
+    V: Element,
+    Q: Borrow<K::Borrowed>,
+    R: Borrow<V::Borrowed>,
+{
+    fn from_iter<I: IntoIterator<Item = (Q, R)>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut map = UnorderedMap::new();
+        map.as_mut().reserve(iter.size_hint().0);
+        map.as_mut().extend(iter);
+        map
+    }
+}
+
+impl<K, V> From<HashMap<K::Owned, V::Owned>> for Pin<Box<UnorderedMap<K, V>>>
+where
+    K: UnorderedMapKey<V>,
+    V: Element,
+{
+    fn from(map: HashMap<K::Owned, V::Owned>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V> From<&UnorderedMap<K, V>> for HashMap<K::Owned, V::Owned>
+where
+    K: UnorderedMapKey<V>,
+    V: Element,
+    K::Owned: Eq + Hash,
+{
+    fn from(map: &UnorderedMap<K, V>) -> Self {
+        map.iter()
+            .map(|(key, value)| (key.to_rust(), value.to_rust()))
+            .collect()
+    }
+}
+
+pub struct Iter<'a, K: UnorderedMapKey<V>, V: Element> {
+    map: PhantomData<&'a UnorderedMap<K, V>>,
+    it: RawIterator,
+    len: usize,
+}
+
+impl<'a, K: UnorderedMapKey<V>, V: Element> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        unsafe {
+            let (key, value) = K::next(&mut self.it);
+            Some((&*key, &*value))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K: UnorderedMapKey<V>, V: Element> ExactSizeIterator for Iter<'a, K, V> {}
+
+impl UnorderedMapKey<i64> for StlString {
+    unsafe fn construct(map: *mut UnorderedMap<Self, i64>) {
+        cpp!([map as "std::unordered_map<std::string, int64_t>*"] {
+            new (map) std::unordered_map<std::string, int64_t>();
+        })
+    }
+
+    unsafe fn destruct(map: &mut UnorderedMap<Self, i64>) {
+        cpp!([map as "std::unordered_map<std::string, int64_t>*"] {
+            map->~unordered_map();
+        })
+    }
+
+    fn size(map: &UnorderedMap<Self, i64>) -> usize {
+        unsafe {
+            cpp!([map as "const std::unordered_map<std::string, int64_t>*"] -> size_t as "size_t" {
+                return map->size();
+            })
+        }
+    }
+
+    fn find(map: &UnorderedMap<Self, i64>, key: &str) -> *const i64 {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::unordered_map<std::string, int64_t>*",
+                  ptr as "const char*", len as "size_t"] -> *const i64 as "const int64_t*" {
+                auto it = map->find(std::string(ptr, len));
+                return it == map->end() ? nullptr : &it->second;
+            })
+        }
+    }
+
+    fn entry(map: &mut UnorderedMap<Self, i64>, key: &str) -> *mut i64 {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "std::unordered_map<std::string, int64_t>*",
+                  ptr as "const char*", len as "size_t"] -> *mut i64 as "int64_t*" {
+                return &(*map)[std::string(ptr, len)];
+            })
+        }
+    }
+
+    fn erase(map: &mut UnorderedMap<Self, i64>, key: &str) -> usize {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "std::unordered_map<std::string, int64_t>*",
+                  ptr as "const char*", len as "size_t"] -> size_t as "size_t" {
+                return map->erase(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn clear(map: &mut UnorderedMap<Self, i64>) {
+        unsafe {
+            cpp!([map as "std::unordered_map<std::string, int64_t>*"] {
+                map->clear();
+            })
+        }
+    }
+
+    fn swap(map: &mut UnorderedMap<Self, i64>, other: &mut UnorderedMap<Self, i64>) {
+        unsafe {
+            cpp!([map as "std::unordered_map<std::string, int64_t>*",
+                  other as "std::unordered_map<std::string, int64_t>*"] {
+                map->swap(*other);
+            })
+        }
+    }
+
+    fn reserve(map: &mut UnorderedMap<Self, i64>, count: usize) {
+        let count = count as size_t;
+        unsafe {
+            cpp!([map as "std::unordered_map<std::string, int64_t>*", count as "size_t"] {
+                map->reserve(count);
+            })
+        }
+    }
+
+    fn load_factor(map: &UnorderedMap<Self, i64>) -> f32 {
+        unsafe {
+            cpp!([map as "const std::unordered_map<std::string, int64_t>*"] -> f32 as "float" {
+                return map->load_factor();
+            })
+        }
+    }
+
+    fn bucket_count(map: &UnorderedMap<Self, i64>) -> usize {
+        unsafe {
+            cpp!([map as "const std::unordered_map<std::string, int64_t>*"] -> size_t as "size_t" {
+                return map->bucket_count();
+            })
+        }
+    }
+
+    fn begin(map: &UnorderedMap<Self, i64>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::unordered_map<std::string, int64_t>*",
+                  it as "std::unordered_map<std::string, int64_t>::const_iterator*"] {
+                *it = map->begin();
+            })
+        }
+    }
+
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const i64) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const i64 = ptr::null();
+        cpp!([it as "std::unordered_map<std::string, int64_t>::const_iterator*",
+              mut key as "const std::string*", mut value as "const int64_t*"] {
+            key = &(*it)->first;
+            value = &(*it)->second;
+            ++*it;
+        });
+        (key, value)
+    }
+}
+
+impl UnorderedMapKey<StlString> for i64 {
+    unsafe fn construct(map: *mut UnorderedMap<Self, StlString>) {
+        cpp!([map as "std::unordered_map<int64_t, std::string>*"] {
+            new (map) std::unordered_map<int64_t, std::string>();
+        })
+    }
+
+    unsafe fn destruct(map: &mut UnorderedMap<Self, StlString>) {
+        cpp!([map as "std::unordered_map<int64_t, std::string>*"] {
+            map->~unordered_map();
+        })
+    }
+
+    fn size(map: &UnorderedMap<Self, StlString>) -> usize {
+        unsafe {
+            cpp!([map as "const std::unordered_map<int64_t, std::string>*"] -> size_t as "size_t" {
+                return map->size();
+            })
+        }
+    }
+
+    fn find(map: &UnorderedMap<Self, StlString>, key: &i64) -> *const StlString {
+        let key = *key;
+        unsafe {
+            cpp!([map as "const std::unordered_map<int64_t, std::string>*", key as "int64_t"]
+                  -> *const StlString as "const std::string*" {
+                auto it = map->find(key);
+                return it == map->end() ? nullptr : &it->second;
+            })
+        }
+    }
+
+    fn entry(map: &mut UnorderedMap<Self, StlString>, key: &i64) -> *mut StlString {
+        let key = *key;
+        unsafe {
+            cpp!([map as "std::unordered_map<int64_t, std::string>*", key as "int64_t"]
+                  -> *mut StlString as "std::string*" {
+                return &(*map)[key];
+            })
+        }
+    }
+
+    fn erase(map: &mut UnorderedMap<Self, StlString>, key: &i64) -> usize {
+        let key = *key;
+        unsafe {
+            cpp!([map as "std::unordered_map<int64_t, std::string>*", key as "int64_t"]
+                  -> size_t as "size_t" {
+                return map->erase(key);
+            })
+        }
+    }
+
+    fn clear(map: &mut UnorderedMap<Self, StlString>) {
+        unsafe {
+            cpp!([map as "std::unordered_map<int64_t, std::string>*"] {
+                map->clear();
+            })
+        }
+    }
+
+    fn swap(map: &mut UnorderedMap<Self, StlString>, other: &mut UnorderedMap<Self, StlString>) {
+        unsafe {
+            cpp!([map as "std::unordered_map<int64_t, std::string>*",
+                  other as "std::unordered_map<int64_t, std::string>*"] {
+                map->swap(*other);
+            })
+        }
+    }
+
+    fn reserve(map: &mut UnorderedMap<Self, StlString>, count: usize) {
+        let count = count as size_t;
+        unsafe {
+            cpp!([map as "std::unordered_map<int64_t, std::string>*", count as "size_t"] {
+                map->reserve(count);
+            })
+        }
+    }
+
+    fn load_factor(map: &UnorderedMap<Self, StlString>) -> f32 {
+        unsafe {
+            cpp!([map as "const std::unordered_map<int64_t, std::string>*"] -> f32 as "float" {
+                return map->load_factor();
+            })
+        }
+    }
+
+    fn bucket_count(map: &UnorderedMap<Self, StlString>) -> usize {
+        unsafe {
+            cpp!([map as "const std::unordered_map<int64_t, std::string>*"] -> size_t as "size_t" {
+                return map->bucket_count();
+            })
+        }
+    }
+
+    fn begin(map: &UnorderedMap<Self, StlString>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::unordered_map<int64_t, std::string>*",
+                  it as "std::unordered_map<int64_t, std::string>::const_iterator*"] {
+                *it = map->begin();
+            })
+        }
+    }
+
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const StlString) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const StlString = ptr::null();
+        cpp!([it as "std::unordered_map<int64_t, std::string>::const_iterator*",
+              mut key as "const int64_t*", mut value as "const std::string*"] {
+            key = &(*it)->first;
+            value = &(*it)->second;
+            ++*it;
+        });
+        (key, value)
+    }
+}
+
+/// Key types of `std::unordered_multimap<K, V>` for which the instantiation exists in C++.
+pub trait UnorderedMultiMapKey<V: Element>: Element + Sized {
+    /// Constructs an empty multimap at `map`.
+    ///
+    /// # Safety
+    ///
+    /// `map` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(map: *mut UnorderedMultiMap<Self, V>);
+
+    /// Destroys `map`.
+    ///
+    /// # Safety
+    ///
+    /// `map` must not be used after this call.
+    unsafe fn destruct(map: &mut UnorderedMultiMap<Self, V>);
+
+    fn size(map: &UnorderedMultiMap<Self, V>) -> usize;
+
+    fn count(map: &UnorderedMultiMap<Self, V>, key: &Self::Borrowed) -> usize;
+
+    fn insert(map: &mut UnorderedMultiMap<Self, V>, key: &Self::Borrowed, value: &V::Borrowed);
+
+    fn erase(map: &mut UnorderedMultiMap<Self, V>, key: &Self::Borrowed) -> usize;
+
+    /// Removes the entry at `it`.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an entry of `map`.
+    unsafe fn erase_at(map: &mut UnorderedMultiMap<Self, V>, it: &RawIterator);
+
+    fn clear(map: &mut UnorderedMultiMap<Self, V>);
+
+    fn bucket_count(map: &UnorderedMultiMap<Self, V>) -> usize;
+
+    fn begin(map: &UnorderedMultiMap<Self, V>, it: &mut RawIterator);
+
+    /// Moves `it` to the first entry for `key`, which the others follow.
+    fn equal_range(map: &UnorderedMultiMap<Self, V>, key: &Self::Borrowed, it: &mut RawIterator);
+
+    /// Returns the entry at `it` and moves `it` to the next entry.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an entry of a multimap.
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const V);
+}
+
+/// `std::unordered_multimap<K, V>`.
+///
+/// It is laid out as `std::unordered_map<K, V>`, so it is pinned like `UnorderedMap`.
+#[repr(C)]
+pub struct UnorderedMultiMap<K: UnorderedMultiMapKey<V>, V: Element>(
+    unordered_map_of_dummy,
+    PhantomData<(K, V)>,
+    PhantomPinned,
+);
+
+impl<K: UnorderedMultiMapKey<V>, V: Element> UnorderedMultiMap<K, V> {
+    pub fn new() -> Pin<Box<Self>> {
+        let map = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            K::construct(map);
+            Box::into_pin(Box::from_raw(map))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        K::size(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of entries for `key`.
+    pub fn count(&self, key: &K::Borrowed) -> usize {
+        K::count(self, key)
+    }
+
+    pub fn contains_key(&self, key: &K::Borrowed) -> bool {
+        self.count(key) != 0
+    }
+
+    pub fn insert(self: Pin<&mut Self>, key: &K::Borrowed, value: &V::Borrowed) {
+        K::insert(unsafe { self.get_unchecked_mut() }, key, value);
+    }
+
+    /// Removes every entry for `key`, returning how many there were.
+    pub fn remove_all(self: Pin<&mut Self>, key: &K::Borrowed) -> usize {
+        K::erase(unsafe { self.get_unchecked_mut() }, key)
+    }
+
+    /// Removes an entry of `key` whose value equals `value`, returning whether one was
+    /// found.
+    pub fn remove_entry(self: Pin<&mut Self>, key: &K::Borrowed, value: &V::Borrowed) -> bool
+    where
+        V::Borrowed: PartialEq,
+    {
+        let mut iter = self.equal_range(key);
+        loop {
+            let it = iter.it;
+            match iter.next() {
+                Some((_, found)) if Borrow::<V::Borrowed>::borrow(&found.to_rust()) == value => {
+                    unsafe { K::erase_at(self.get_unchecked_mut(), &it) };
+                    return true;
+                }
+                Some(_) => {}
+                None => return false,
+            }
+        }
+    }
+
+    pub fn clear(self: Pin<&mut Self>) {
+        K::clear(unsafe { self.get_unchecked_mut() });
+    }
+
+    pub fn bucket_count(&self) -> usize {
+        K::bucket_count(self)
+    }
+
+    /// Returns the entries in the order C++ iterates them, which groups them by bucket.
+    pub fn iter(&self) -> MultiIter<'_, K, V> {
+        let mut iter = MultiIter {
+            map: PhantomData,
+            it: RawIterator::default(),
+            len: self.len(),
+        };
+        K::begin(self, &mut iter.it);
+        iter
+    }
+
+    /// Returns the entries for `key`, which are adjacent in iteration order.
+    pub fn equal_range(&self, key: &K::Borrowed) -> MultiIter<'_, K, V> {
+        let mut iter = MultiIter {
+            map: PhantomData,
+            it: RawIterator::default(),
+            len: self.count(key),
+        };
+        K::equal_range(self, key, &mut iter.it);
+        iter
+    }
+}
+
+impl<K: UnorderedMultiMapKey<V>, V: Element> Drop for UnorderedMultiMap<K, V> {
+    fn drop(&mut self) {
+        unsafe { K::destruct(self) }
+    }
+}
+
+impl<K, V> fmt::Debug for UnorderedMultiMap<K, V>
+where
+    K: UnorderedMultiMapKey<V> + fmt::Debug,
+    V: Element + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K: UnorderedMultiMapKey<V>, V: Element> IntoIterator for &'a UnorderedMultiMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MultiIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, Q, R> Extend<(Q, R)> for Pin<&mut UnorderedMultiMap<K, V>>
+where
+    K: UnorderedMultiMapKey<V>,
+    V: Element,
+    Q: Borrow<K::Borrowed>,
+    R: Borrow<V::Borrowed>,
+{
+    fn extend<I: IntoIterator<Item = (Q, R)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.as_mut().insert(key.borrow(), value.borrow());
+        }
+    }
+}
+
+impl<K, V, Q, R> FromIterator<(Q, R)> for Pin<Box<UnorderedMultiMap<K, V>>>
+where
+    K: UnorderedMultiMapKey<V>,
+    V: Element,
+    Q: Borrow<K::Borrowed>,
+    R: Borrow<V::Borrowed>,
+{
+    fn from_iter<I: IntoIterator<Item = (Q, R)>>(iter: I) -> Self {
+        let mut map = UnorderedMultiMap::new();
+        map.as_mut().extend(iter);
+        map
+    }
+}
+
+pub struct MultiIter<'a, K: UnorderedMultiMapKey<V>, V: Element> {
+    map: PhantomData<&'a UnorderedMultiMap<K, V>>,
+    it: RawIterator,
+    len: usize,
+}
+
+impl<'a, K: UnorderedMultiMapKey<V>, V: Element> Iterator for MultiIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        unsafe {
+            let (key, value) = K::next(&mut self.it);
+            Some((&*key, &*value))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K: UnorderedMultiMapKey<V>, V: Element> ExactSizeIterator for MultiIter<'a, K, V> {}
+
+impl UnorderedMultiMapKey<i64> for StlString {
+    unsafe fn construct(map: *mut UnorderedMultiMap<Self, i64>) {
+        cpp!([map as "std::unordered_multimap<std::string, int64_t>*"] {
+            new (map) std::unordered_multimap<std::string, int64_t>();
+        })
+    }
+
+    unsafe fn destruct(map: &mut UnorderedMultiMap<Self, i64>) {
+        cpp!([map as "std::unordered_multimap<std::string, int64_t>*"] {
+            map->~unordered_multimap();
+        })
+    }
+
+    fn size(map: &UnorderedMultiMap<Self, i64>) -> usize {
+        unsafe {
+            cpp!([map as "const std::unordered_multimap<std::string, int64_t>*"] -> size_t as "size_t" {
+                return map->size();
+            })
+        }
+    }
+
+    fn count(map: &UnorderedMultiMap<Self, i64>, key: &str) -> usize {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::unordered_multimap<std::string, int64_t>*",
+                  ptr as "const char*", len as "size_t"] -> size_t as "size_t" {
+                return map->count(std::string(ptr, len));
+            })
+        }
+    }
+
+    fn insert(map: &mut UnorderedMultiMap<Self, i64>, key: &str, value: &i64) {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        let value = *value;
+        unsafe {
+            cpp!([map as "std::unordered_multimap<std::string, int64_t>*",
+                  ptr as "const char*", len as "size_t", value as "int64_t"] {
+                map->emplace(std::string(ptr, len), value);
+            })
+        }
+    }
+
+    fn erase(map: &mut UnorderedMultiMap<Self, i64>, key: &str) -> usize {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "std::unordered_multimap<std::string, int64_t>*",
+                  ptr as "const char*", len as "size_t"] -> size_t as "size_t" {
+                return map->erase(std::string(ptr, len));
+            })
+        }
+    }
+
+    unsafe fn erase_at(map: &mut UnorderedMultiMap<Self, i64>, it: &RawIterator) {
+        cpp!([map as "std::unordered_multimap<std::string, int64_t>*",
+              it as "const std::unordered_multimap<std::string, int64_t>::const_iterator*"] {
+            map->erase(*it);
+        })
+    }
+
+    fn clear(map: &mut UnorderedMultiMap<Self, i64>) {
+        unsafe {
+            cpp!([map as "std::unordered_multimap<std::string, int64_t>*"] {
+                map->clear();
+            })
+        }
+    }
+
+    fn bucket_count(map: &UnorderedMultiMap<Self, i64>) -> usize {
+        unsafe {
+            cpp!([map as "const std::unordered_multimap<std::string, int64_t>*"] -> size_t as "size_t" {
+                return map->bucket_count();
+            })
+        }
+    }
+
+    fn begin(map: &UnorderedMultiMap<Self, i64>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([map as "const std::unordered_multimap<std::string, int64_t>*",
+                  it as "std::unordered_multimap<std::string, int64_t>::const_iterator*"] {
+                *it = map->begin();
+            })
+        }
+    }
+
+    fn equal_range(map: &UnorderedMultiMap<Self, i64>, key: &str, it: &mut RawIterator) {
+        let ptr = key.as_ptr();
+        let len = key.len() as size_t;
+        unsafe {
+            cpp!([map as "const std::unordered_multimap<std::string, int64_t>*",
+                  ptr as "const char*", len as "size_t",
+                  it as "std::unordered_multimap<std::string, int64_t>::const_iterator*"] {
+                *it = map->equal_range(std::string(ptr, len)).first;
+            })
+        }
+    }
+
+    unsafe fn next(it: &mut RawIterator) -> (*const Self, *const i64) {
+        let mut key: *const Self = ptr::null();
+        let mut value: *const i64 = ptr::null();
+        cpp!([it as "std::unordered_multimap<std::string, int64_t>::const_iterator*",
+              mut key as "const std::string*", mut value as "const int64_t*"] {
+            key = &(*it)->first;
+            value = &(*it)->second;
+            ++*it;
+        });
+        (key, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    struct StructWithUnorderedMaps {
+        counts: UnorderedMap<StlString, i64>,
+        labels: UnorderedMap<i64, StlString>,
+    }
+
+    #[test]
+    fn unittest_unordered_map() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithUnorderedMaps as "struct_with_unordered_maps*" {
+                static struct_with_unordered_maps x{
+                    {{"apple", 3}, {"banana", 5}}, {{1, "one"}, {2, "two"}}
+                };
+                return &x;
+            })
+        };
+
+        assert_eq!(x.counts.len(), 2);
+        assert_eq!(x.counts.get("apple"), Some(&3));
+        assert_eq!(x.counts.get("cherry"), None);
+        assert_eq!(x.labels.get(&2).unwrap().to_rust(), "two");
+
+        let mut counts = unsafe { Pin::new_unchecked(&mut x.counts) };
+        *counts.as_mut().entry("apple") += 1;
+        *counts.as_mut().entry("cherry") += 7;
+        assert_eq!(counts.as_mut().insert("banana", &6), Some(5));
+        let mut labels = unsafe { Pin::new_unchecked(&mut x.labels) };
+        assert_eq!(labels.as_mut().remove(&1), Some("one".to_owned()));
+        assert_eq!(labels.as_mut().remove(&1), None);
+        labels.extend(vec![(3, "three"), (4, "four")]);
+
+        assert_eq!(
+            HashMap::from(&x.counts),
+            vec![("apple", 4), ("banana", 6), ("cherry", 7)]
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect()
+        );
+        assert_eq!(x.labels.iter().len(), 3);
+
+        let x: &StructWithUnorderedMaps = x;
+        let found = unsafe {
+            cpp!([x as "const struct_with_unordered_maps*"] -> i64 as "int64_t" {
+                return x->counts.at("cherry") + x->labels.at(4).size();
+            })
+        };
+        assert_eq!(found, 11);
+    }
+
+    #[test]
+    fn unittest_unordered_map_buckets() {
+        let mut map: Pin<Box<UnorderedMap<i64, StlString>>> = UnorderedMap::new();
+        assert!(map.is_empty());
+
+        map.as_mut().reserve(100);
+        assert!(map.bucket_count() >= 100);
+
+        map.as_mut().extend((0..50).map(|i| (i, i.to_string())));
+        assert_eq!(map.len(), 50);
+        assert_eq!(
+            map.load_factor(),
+            map.len() as f32 / map.bucket_count() as f32
+        );
+
+        let labels: HashMap<i64, String> = HashMap::from(&*map);
+        let copy: Pin<Box<UnorderedMap<i64, StlString>>> = labels.clone().into();
+        assert_eq!(HashMap::from(&*copy), labels);
+        assert_eq!(copy.get(&42).unwrap().to_rust(), "42");
+    }
+
+    #[repr(C)]
+    struct StructWithUnorderedMultiMaps {
+        tags: UnorderedMultiMap<StlString, i64>,
+    }
+
+    #[test]
+    fn unittest_unordered_multimap() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithUnorderedMultiMaps as "struct_with_unordered_multimaps*" {
+                static struct_with_unordered_multimaps x{{{"red", 1}, {"blue", 2}, {"red", 3}}};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.tags.len(), 3);
+        assert_eq!(x.tags.count("red"), 2);
+        assert!(!x.tags.contains_key("green"));
+
+        let mut reds: Vec<_> = x.tags.equal_range("red").map(|(_, value)| *value).collect();
+        reds.sort();
+        assert_eq!(reds, [1, 3]);
+        assert_eq!(x.tags.equal_range("green").len(), 0);
+
+        let mut tags = unsafe { Pin::new_unchecked(&mut x.tags) };
+        tags.as_mut().insert("green", &4);
+        tags.as_mut().insert("red", &5);
+        assert!(tags.as_mut().remove_entry("red", &3));
+        assert!(!tags.as_mut().remove_entry("red", &3));
+        assert_eq!(tags.as_mut().remove_all("blue"), 1);
+        assert!(x.tags.bucket_count() > 0);
+
+        let keys: Vec<_> = x.tags.iter().map(|(key, _)| key.to_rust()).collect();
+        assert_eq!(keys.len(), 3);
+        let first_red = keys.iter().position(|key| key == "red").unwrap();
+        assert_eq!(keys[first_red + 1], "red");
+
+        let x: &StructWithUnorderedMultiMaps = x;
+        let sum = unsafe {
+            cpp!([x as "const struct_with_unordered_multimaps*"] -> i64 as "int64_t" {
+                int64_t sum = 0;
+                auto range = x->tags.equal_range("red");
+                for (auto it = range.first; it != range.second; ++it) {
+                    sum += it->second;
+                }
+                return sum * 10 + x->tags.count("green");
+            })
+        };
+        assert_eq!(sum, 61);
+
+        let map: Pin<Box<UnorderedMultiMap<StlString, i64>>> =
+            vec![("a", 1), ("a", 1)].into_iter().collect();
+        assert_eq!(format!("{:?}", map), r#"{"a": 1, "a": 1}"#);
+    }
+}
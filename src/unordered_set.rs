@@ -0,0 +1,461 @@
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::marker::{PhantomData, PhantomPinned};
+use std::mem::MaybeUninit;
+use std::pin::Pin;
+
+use libc::size_t;
+
+use crate::bindings::root::rust::*;
+use crate::element::Element;
+
+cpp! {{
+    #include <unordered_set>
+
+    #include "wrapper.hpp"
+
+    static_assert(sizeof(std::unordered_set<uint64_t>) == sizeof(rust::unordered_set_of_dummy), "");
+    static_assert(
+        sizeof(std::unordered_set<uint64_t>::const_iterator)
+            == sizeof(rust::unordered_set_iterator_of_dummy), "");
+
+    struct struct_with_unordered_sets {
+        std::unordered_set<uint64_t> members;
+    };
+}}
+
+/// Opaque `std::unordered_set<T>::const_iterator`.
+#[repr(C)]
+#[derive(Default)]
+pub struct RawIterator(unordered_set_iterator_of_dummy);
+
+/// Element types of `std::unordered_set<T>` for which the instantiation exists in C++.
+///
+/// Elements are always hashed and compared in C++, so both languages find the same elements.
+pub trait UnorderedSetElement: Element + Sized {
+    /// Constructs an empty set at `set`.
+    ///
+    /// # Safety
+    ///
+    /// `set` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(set: *mut UnorderedSet<Self>);
+
+    /// Destroys `set`.
+    ///
+    /// # Safety
+    ///
+    /// `set` must not be used after this call.
+    unsafe fn destruct(set: &mut UnorderedSet<Self>);
+
+    fn size(set: &UnorderedSet<Self>) -> usize;
+
+    fn contains(set: &UnorderedSet<Self>, value: &Self::Borrowed) -> bool;
+
+    fn insert(set: &mut UnorderedSet<Self>, value: &Self::Borrowed) -> bool;
+
+    fn erase(set: &mut UnorderedSet<Self>, value: &Self::Borrowed) -> usize;
+
+    fn clear(set: &mut UnorderedSet<Self>);
+
+    fn swap(set: &mut UnorderedSet<Self>, other: &mut UnorderedSet<Self>);
+
+    fn reserve(set: &mut UnorderedSet<Self>, count: usize);
+
+    fn begin(set: &UnorderedSet<Self>, it: &mut RawIterator);
+
+    /// Returns the element at `it` and moves `it` to the next element.
+    ///
+    /// # Safety
+    ///
+    /// `it` must point to an element of a set.
+    unsafe fn next(it: &mut RawIterator) -> *const Self;
+
+    /// Inserts the elements of `set` or `other` into `out`.
+    fn union(set: &UnorderedSet<Self>, other: &UnorderedSet<Self>, out: &mut UnorderedSet<Self>);
+
+    /// Inserts the elements of both `set` and `other` into `out`.
+    fn intersection(
+        set: &UnorderedSet<Self>,
+        other: &UnorderedSet<Self>,
+        out: &mut UnorderedSet<Self>,
+    );
+
+    /// Inserts the elements of `set` but not of `other` into `out`.
+    fn difference(
+        set: &UnorderedSet<Self>,
+        other: &UnorderedSet<Self>,
+        out: &mut UnorderedSet<Self>,
+    );
+}
+
+/// `std::unordered_set<T>`.
+///
+/// It is laid out like `std::unordered_map`, so it is pinned like `UnorderedMap`.
+#[repr(C)]
+pub struct UnorderedSet<T: UnorderedSetElement>(
+    unordered_set_of_dummy,
+    PhantomData<T>,
+    PhantomPinned,
+);
+
+impl<T: UnorderedSetElement> UnorderedSet<T> {
+    pub fn new() -> Pin<Box<Self>> {
+        let set = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            T::construct(set);
+            Box::into_pin(Box::from_raw(set))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        T::size(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, value: &T::Borrowed) -> bool {
+        T::contains(self, value)
+    }
+
+    /// Inserts `value`, returning whether it was not present.
+    pub fn insert(self: Pin<&mut Self>, value: &T::Borrowed) -> bool {
+        T::insert(unsafe { self.get_unchecked_mut() }, value)
+    }
+
+    /// Removes `value`, returning whether it was present.
+    pub fn remove(self: Pin<&mut Self>, value: &T::Borrowed) -> bool {
+        T::erase(unsafe { self.get_unchecked_mut() }, value) > 0
+    }
+
+    pub fn clear(self: Pin<&mut Self>) {
+        T::clear(unsafe { self.get_unchecked_mut() });
+    }
+
+    /// Exchanges the contents with `other` without moving either set object.
+    pub fn swap(self: Pin<&mut Self>, other: Pin<&mut Self>) {
+        unsafe { T::swap(self.get_unchecked_mut(), other.get_unchecked_mut()) };
+    }
+
+    /// Rehashes the set so that it can hold at least `count` elements without rehashing.
+    pub fn reserve(self: Pin<&mut Self>, count: usize) {
+        T::reserve(unsafe { self.get_unchecked_mut() }, count);
+    }
+
+    /// Returns the elements in the order C++ iterates them, which groups them by bucket.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            set: PhantomData,
+            it: RawIterator::default(),
+            len: self.len(),
+        };
+        T::begin(self, &mut iter.it);
+        iter
+    }
+
+    pub fn union(&self, other: &Self) -> Pin<Box<Self>> {
+        let mut out = Self::new();
+        T::union(self, other, unsafe { out.as_mut().get_unchecked_mut() });
+        out
+    }
+
+    pub fn intersection(&self, other: &Self) -> Pin<Box<Self>> {
+        let mut out = Self::new();
+        T::intersection(self, other, unsafe { out.as_mut().get_unchecked_mut() });
+        out
+    }
+
+    pub fn difference(&self, other: &Self) -> Pin<Box<Self>> {
+        let mut out = Self::new();
+        T::difference(self, other, unsafe { out.as_mut().get_unchecked_mut() });
+        out
+    }
+}
+
+impl<T: UnorderedSetElement> Drop for UnorderedSet<T> {
+    fn drop(&mut self) {
+        unsafe { T::destruct(self) }
+    }
+}
+
+impl<T> fmt::Debug for UnorderedSet<T>
+where
+    T: UnorderedSetElement + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: UnorderedSetElement> IntoIterator for &'a UnorderedSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, Q> Extend<Q> for Pin<&mut UnorderedSet<T>>
+where
+    T: UnorderedSetElement,
+    Q: Borrow<T::Borrowed>,
+{
+    fn extend<I: IntoIterator<Item = Q>>(&mut self, iter: I) {
+        for value in iter {
+            self.as_mut().insert(value.borrow());
+        }
+    }
+}
+
+impl<T, Q> FromIterator<Q> for Pin<Box<UnorderedSet<T>>>
+where
+    T: UnorderedSetElement,
+    Q: Borrow<T::Borrowed>,
+{
+    fn from_iter<I: IntoIterator<Item = Q>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut set = UnorderedSet::new();
+        set.as_mut().reserve(iter.size_hint().0);
+        set.as_mut().extend(iter);
+        set
+    }
+}
+
+impl<T: UnorderedSetElement> From<HashSet<T::Owned>> for Pin<Box<UnorderedSet<T>>> {
+    fn from(set: HashSet<T::Owned>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T> From<&UnorderedSet<T>> for HashSet<T::Owned>
+where
+    T: UnorderedSetElement,
+    T::Owned: Eq + Hash,
+{
+    fn from(set: &UnorderedSet<T>) -> Self {
+        set.iter().map(T::to_rust).collect()
+    }
+}
+
+pub struct Iter<'a, T: UnorderedSetElement> {
+    set: PhantomData<&'a UnorderedSet<T>>,
+    it: RawIterator,
+    len: usize,
+}
+
+impl<'a, T: UnorderedSetElement> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        unsafe { Some(&*T::next(&mut self.it)) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T: UnorderedSetElement> ExactSizeIterator for Iter<'a, T> {}
+
+impl UnorderedSetElement for u64 {
+    unsafe fn construct(set: *mut UnorderedSet<Self>) {
+        cpp!([set as "std::unordered_set<uint64_t>*"] {
+            new (set) std::unordered_set<uint64_t>();
+        })
+    }
+
+    unsafe fn destruct(set: &mut UnorderedSet<Self>) {
+        cpp!([set as "std::unordered_set<uint64_t>*"] {
+            set->~unordered_set();
+        })
+    }
+
+    fn size(set: &UnorderedSet<Self>) -> usize {
+        unsafe {
+            cpp!([set as "const std::unordered_set<uint64_t>*"] -> size_t as "size_t" {
+                return set->size();
+            })
+        }
+    }
+
+    fn contains(set: &UnorderedSet<Self>, value: &u64) -> bool {
+        let value = *value;
+        unsafe {
+            cpp!([set as "const std::unordered_set<uint64_t>*", value as "uint64_t"]
+                  -> bool as "bool" {
+                return set->count(value) > 0;
+            })
+        }
+    }
+
+    fn insert(set: &mut UnorderedSet<Self>, value: &u64) -> bool {
+        let value = *value;
+        unsafe {
+            cpp!([set as "std::unordered_set<uint64_t>*", value as "uint64_t"] -> bool as "bool" {
+                return set->insert(value).second;
+            })
+        }
+    }
+
+    fn erase(set: &mut UnorderedSet<Self>, value: &u64) -> usize {
+        let value = *value;
+        unsafe {
+            cpp!([set as "std::unordered_set<uint64_t>*", value as "uint64_t"]
+                  -> size_t as "size_t" {
+                return set->erase(value);
+            })
+        }
+    }
+
+    fn clear(set: &mut UnorderedSet<Self>) {
+        unsafe {
+            cpp!([set as "std::unordered_set<uint64_t>*"] {
+                set->clear();
+            })
+        }
+    }
+
+    fn swap(set: &mut UnorderedSet<Self>, other: &mut UnorderedSet<Self>) {
+        unsafe {
+            cpp!([set as "std::unordered_set<uint64_t>*", other as "std::unordered_set<uint64_t>*"] {
+                set->swap(*other);
+            })
+        }
+    }
+
+    fn reserve(set: &mut UnorderedSet<Self>, count: usize) {
+        let count = count as size_t;
+        unsafe {
+            cpp!([set as "std::unordered_set<uint64_t>*", count as "size_t"] {
+                set->reserve(count);
+            })
+        }
+    }
+
+    fn begin(set: &UnorderedSet<Self>, it: &mut RawIterator) {
+        unsafe {
+            cpp!([set as "const std::unordered_set<uint64_t>*",
+                  it as "std::unordered_set<uint64_t>::const_iterator*"] {
+                *it = set->begin();
+            })
+        }
+    }
+
+    unsafe fn next(it: &mut RawIterator) -> *const Self {
+        cpp!([it as "std::unordered_set<uint64_t>::const_iterator*"]
+              -> *const u64 as "const uint64_t*" {
+            return &*(*it)++;
+        })
+    }
+
+    fn union(set: &UnorderedSet<Self>, other: &UnorderedSet<Self>, out: &mut UnorderedSet<Self>) {
+        unsafe {
+            cpp!([set as "const std::unordered_set<uint64_t>*",
+                  other as "const std::unordered_set<uint64_t>*",
+                  out as "std::unordered_set<uint64_t>*"] {
+                out->insert(set->begin(), set->end());
+                out->insert(other->begin(), other->end());
+            })
+        }
+    }
+
+    fn intersection(
+        set: &UnorderedSet<Self>,
+        other: &UnorderedSet<Self>,
+        out: &mut UnorderedSet<Self>,
+    ) {
+        unsafe {
+            cpp!([set as "const std::unordered_set<uint64_t>*",
+                  other as "const std::unordered_set<uint64_t>*",
+                  out as "std::unordered_set<uint64_t>*"] {
+                for (auto value : *set) {
+                    if (other->count(value) > 0) {
+                        out->insert(value);
+                    }
+                }
+            })
+        }
+    }
+
+    fn difference(
+        set: &UnorderedSet<Self>,
+        other: &UnorderedSet<Self>,
+        out: &mut UnorderedSet<Self>,
+    ) {
+        unsafe {
+            cpp!([set as "const std::unordered_set<uint64_t>*",
+                  other as "const std::unordered_set<uint64_t>*",
+                  out as "std::unordered_set<uint64_t>*"] {
+                for (auto value : *set) {
+                    if (other->count(value) == 0) {
+                        out->insert(value);
+                    }
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    struct StructWithUnorderedSets {
+        members: UnorderedSet<u64>,
+    }
+
+    #[test]
+    fn unittest_unordered_set() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithUnorderedSets as "struct_with_unordered_sets*" {
+                static struct_with_unordered_sets x{{1, 2, 3}};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.members.len(), 3);
+        assert!(x.members.contains(&2));
+        let mut members = unsafe { Pin::new_unchecked(&mut x.members) };
+        assert!(members.as_mut().insert(&10));
+        assert!(!members.as_mut().insert(&10));
+        assert!(members.as_mut().remove(&1));
+        assert_eq!(
+            HashSet::from(&x.members),
+            vec![2, 3, 10].into_iter().collect()
+        );
+
+        let x: &StructWithUnorderedSets = x;
+        let found = unsafe {
+            cpp!([x as "const struct_with_unordered_sets*"] -> bool as "bool" {
+                return x->members.count(10) == 1 && x->members.count(1) == 0;
+            })
+        };
+        assert!(found);
+
+        let other: Pin<Box<UnorderedSet<u64>>> = vec![3u64, 4].into_iter().collect();
+        assert_eq!(
+            HashSet::from(&*x.members.union(&other)),
+            vec![2, 3, 4, 10].into_iter().collect()
+        );
+        assert_eq!(
+            HashSet::from(&*x.members.intersection(&other)),
+            vec![3].into_iter().collect()
+        );
+        assert_eq!(
+            HashSet::from(&*x.members.difference(&other)),
+            vec![2, 10].into_iter().collect()
+        );
+        assert_eq!(x.members.iter().len(), 3);
+    }
+}
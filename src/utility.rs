@@ -0,0 +1,124 @@
+use crate::element::Element;
+
+cpp! {{
+    #include <cstddef>
+    #include <map>
+    #include <string>
+    #include <utility>
+    #include <vector>
+
+    typedef std::pair<int32_t, float> pair_of_int32_t_float;
+    typedef std::pair<int8_t, double> pair_of_int8_t_double;
+
+    static_assert(offsetof(pair_of_int32_t_float, second) == sizeof(int32_t), "");
+    static_assert(offsetof(pair_of_int8_t_double, second) == alignof(double), "");
+
+    struct struct_with_pairs {
+        std::pair<int32_t, float> weight;
+        std::pair<std::string, int64_t> owner;
+        std::vector<std::pair<int32_t, float>> weights;
+        std::map<std::string, std::pair<int32_t, float>> ranks;
+    };
+}}
+
+/// `std::pair<A, B>`, which is laid out as a struct of `first` and `second` by both
+/// libstdc++ and libc++.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pair<A, B> {
+    pub first: A,
+    pub second: B,
+}
+
+impl<A, B> Pair<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Pair { first, second }
+    }
+}
+
+impl<A, B> From<(A, B)> for Pair<A, B> {
+    fn from((first, second): (A, B)) -> Self {
+        Pair { first, second }
+    }
+}
+
+impl<A, B> From<Pair<A, B>> for (A, B) {
+    fn from(pair: Pair<A, B>) -> Self {
+        (pair.first, pair.second)
+    }
+}
+
+impl<A: Copy, B: Copy> Element for Pair<A, B> {
+    type Borrowed = Self;
+    type Owned = Self;
+
+    fn to_rust(&self) -> Self::Owned {
+        *self
+    }
+
+    fn assign(&mut self, value: &Self::Borrowed) {
+        *self = *value;
+    }
+}
+
+#[cfg(all(test, feature = "map", feature = "vector"))]
+mod tests {
+    use super::*;
+    use crate::map::Map;
+    use crate::string::String as StlString;
+    use crate::vector::{VectorInsert, VectorOfPairI32F32, VectorSlice};
+    use std::pin::Pin;
+
+    #[repr(C)]
+    struct StructWithPairs {
+        weight: Pair<i32, f32>,
+        owner: Pair<StlString, i64>,
+        weights: VectorOfPairI32F32,
+        ranks: Map<StlString, Pair<i32, f32>>,
+    }
+
+    #[test]
+    fn unittest_pair() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithPairs as "struct_with_pairs*" {
+                static struct_with_pairs x{{3, 0.5f}, {"boncheol", 42}, {{1, 0.5f}}, {{"bora", {2, 1.0f}}}};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.weight, Pair::new(3, 0.5));
+        assert_eq!(<(i32, f32)>::from(x.weight), (3, 0.5));
+        assert_eq!(x.owner.first.to_string(), "boncheol");
+        assert_eq!(x.owner.second, 42);
+
+        x.weight = (4, 1.5).into();
+        x.owner.first.assign_str("bora");
+
+        assert_eq!(x.weights.as_slice(), &[Pair::new(1, 0.5)]);
+        x.weights.push_back(Pair::new(2, 2.5));
+        x.weights[0].second = 1.0;
+
+        assert_eq!(x.ranks.get("bora"), Some(&Pair::new(2, 1.0)));
+        let mut ranks = unsafe { Pin::new_unchecked(&mut x.ranks) };
+        assert_eq!(
+            ranks.as_mut().insert("bora", &Pair::new(3, 2.0)),
+            Some(Pair::new(2, 1.0))
+        );
+        ranks.as_mut().insert("junmo", &(5, 0.0).into());
+
+        let x: &StructWithPairs = x;
+        let sum = unsafe {
+            cpp!([x as "const struct_with_pairs*"] -> f32 as "float" {
+                float sum = x->weight.first * x->weight.second + x->owner.first.size();
+                for (const auto& weight : x->weights) {
+                    sum += weight.first * weight.second;
+                }
+                for (const auto& rank : x->ranks) {
+                    sum += rank.second.first * rank.second.second;
+                }
+                return sum;
+            })
+        };
+        assert_eq!(sum, 10.0 + 6.0 + 6.0);
+    }
+}
@@ -0,0 +1,509 @@
+use std::mem::ManuallyDrop;
+use std::ptr;
+
+use libc::size_t;
+
+use crate::element::Element;
+use crate::string::String as StlString;
+use crate::vector::VectorOfU8;
+
+cpp! {{
+    #include <algorithm>
+    #include <stdexcept>
+    #include <string>
+    #include <variant>
+    #include <vector>
+
+    static_assert(
+        sizeof(std::variant<int64_t, double, std::string, std::vector<uint8_t>>)
+            == std::max(sizeof(std::string), sizeof(std::vector<uint8_t>)) + alignof(std::string), "");
+
+    // `RawIndex` follows the standard library `build.rs` detects. The index follows the
+    // storage, so a variant of one byte is twice the size of the index.
+    #if defined(CPP_STL_LIBCXX)
+    typedef uint32_t raw_index;
+    #else
+    typedef uint8_t raw_index;
+    #endif
+    static_assert(sizeof(std::variant<uint8_t>) == 2 * sizeof(raw_index), "");
+    static_assert(alignof(std::variant<uint8_t>) == alignof(raw_index), "");
+
+    struct checked {
+        int32_t value;
+
+        checked(int32_t v): value(v) {
+            if (v < 0) {
+                throw std::invalid_argument("negative");
+            }
+        }
+
+        checked(const checked& other): value(other.value) {}
+    };
+
+    struct struct_with_variant {
+        int32_t tag;
+        std::variant<int64_t, double, std::string, std::vector<uint8_t>> message;
+        std::variant<int64_t, checked> checkpoint;
+    };
+}}
+
+/// Index type which libstdc++ uses for variants of less than 255 alternatives.
+#[cfg(not(cpp_stl_libcxx))]
+pub type RawIndex = u8;
+
+/// Index type which libc++ uses unless its unstable ABI picks the smallest type.
+#[cfg(cpp_stl_libcxx)]
+pub type RawIndex = u32;
+
+/// The index of a variant which is valueless by exception, like `std::variant_npos`.
+pub const VARIANT_NPOS: usize = usize::MAX;
+
+/// Storage of the alternatives, which has the size and alignment of the largest one.
+///
+/// More than two alternatives are stored as nested unions.
+#[repr(C)]
+pub union Storage<A, B> {
+    _first: ManuallyDrop<A>,
+    _second: ManuallyDrop<B>,
+}
+
+/// Alternative types of `std::variant<...>` which can be constructed and destroyed in place.
+pub trait Alternative: Element + Sized {
+    /// Constructs a copy of `value` at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(ptr: *mut Self, value: &Self::Borrowed);
+
+    /// Destroys the value at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// The value must not be used after this call.
+    unsafe fn destruct(ptr: *mut Self);
+}
+
+/// The `I`th alternative of a variant declared with `cpp_variant!`.
+pub trait VariantAlternative<const I: usize> {
+    type Type: Alternative;
+}
+
+macro_rules! add_impl {
+    ($($t:ty)*) => ($(
+        impl Alternative for $t {
+            unsafe fn construct(ptr: *mut Self, value: &Self::Borrowed) {
+                ptr::write(ptr, *value);
+            }
+
+            unsafe fn destruct(_ptr: *mut Self) {}
+        }
+    )*)
+}
+
+add_impl!(bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64);
+
+impl Alternative for StlString {
+    unsafe fn construct(ptr: *mut Self, value: &str) {
+        let s = value.as_ptr();
+        let len = value.len() as size_t;
+        cpp!([ptr as "std::string*", s as "const char*", len as "size_t"] {
+            new (ptr) std::string(s, len);
+        })
+    }
+
+    unsafe fn destruct(ptr: *mut Self) {
+        cpp!([ptr as "std::string*"] {
+            ptr->~basic_string();
+        })
+    }
+}
+
+impl Alternative for VectorOfU8 {
+    unsafe fn construct(ptr: *mut Self, value: &[u8]) {
+        let data = value.as_ptr();
+        let len = value.len() as size_t;
+        cpp!([ptr as "std::vector<uint8_t>*", data as "const uint8_t*", len as "size_t"] {
+            new (ptr) std::vector<uint8_t>(data, data + len);
+        })
+    }
+
+    unsafe fn destruct(ptr: *mut Self) {
+        cpp!([ptr as "std::vector<uint8_t>*"] {
+            ptr->~vector();
+        })
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __variant_storage {
+    ($t:ty) => { $t };
+    ($t:ty, $($rest:ty),+) => {
+        $crate::variant::Storage<$t, $crate::__variant_storage!($($rest),+)>
+    };
+}
+
+/// Declares a mirror of a concrete `std::variant<...>` and a view enum borrowing its
+/// active alternative.
+///
+/// The alternatives are listed in the order of the C++ declaration, and each type must
+/// implement `variant::Alternative`. The variant is paired with a `cpp_class!` naming the
+/// C++ type, which `cpp_build` only finds when it is written out in the source. The build
+/// fails unless the C++ type has the size and alignment of the mirror, with the index
+/// following the storage of the alternatives.
+///
+/// ```ignore
+/// cpp_variant! {
+///     /// `std::variant<int64_t, std::string>`.
+///     pub struct Value: cpp_class!(unsafe struct ValueLayout as "std::variant<int64_t, std::string>"),
+///     pub enum ValueRef {
+///         Int(i64),
+///         Text(StlString),
+///     }
+/// }
+/// ```
+///
+/// The variant gets `index()`, `valueless_by_exception()`, `get::<I>()`,
+/// `get_value_mut::<I>()`, `emplace::<I>(value)` and `as_enum()`. `ValueRef` has one
+/// variant per alternative and `ValuelessByException`. Alternatives like `std::string` may
+/// point into themselves, so a variant is pinned like `CppOptional`, and the methods which
+/// modify it take `Pin<&mut Self>`; they are not named `get_mut` and `set` to avoid the
+/// methods of `Pin`.
+#[macro_export]
+macro_rules! cpp_variant {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident: $class:ident!(unsafe struct $layout:ident as $cpp:literal),
+        $view_vis:vis enum $view:ident {
+            $($alternative:ident($t:ty)),+ $(,)?
+        }
+    ) => {
+        $class!(unsafe struct $layout as $cpp);
+
+        $(#[$attr])*
+        #[repr(C)]
+        $vis struct $name {
+            storage: $crate::__variant_storage!($($t),+),
+            index: $crate::variant::RawIndex,
+            _pinned: ::std::marker::PhantomPinned,
+        }
+
+        #[allow(dead_code)]
+        $view_vis enum $view<'a> {
+            $($alternative(&'a $t),)+
+            ValuelessByException,
+        }
+
+        const _: () = {
+            #[allow(non_camel_case_types, dead_code)]
+            enum Index {
+                $($alternative,)+
+                Count,
+            }
+
+            assert!((Index::Count as usize) < $crate::variant::RawIndex::MAX as usize);
+
+            assert!(
+                ::std::mem::size_of::<$name>() == ::std::mem::size_of::<$layout>(),
+                concat!("size of `", stringify!($name), "` differs from sizeof(", $cpp, ")")
+            );
+            assert!(
+                ::std::mem::align_of::<$name>() == ::std::mem::align_of::<$layout>(),
+                concat!("alignment of `", stringify!($name), "` differs from alignof(", $cpp, ")")
+            );
+            assert!(
+                (::std::mem::offset_of!($name, index) + ::std::mem::size_of::<$crate::variant::RawIndex>())
+                    .next_multiple_of(::std::mem::align_of::<$layout>())
+                    == ::std::mem::size_of::<$layout>(),
+                concat!("the index of `", stringify!($name), "` is not at the end of ", $cpp)
+            );
+
+            $(
+                impl $crate::variant::VariantAlternative<{ Index::$alternative as usize }> for $name {
+                    type Type = $t;
+                }
+            )+
+
+            #[allow(dead_code)]
+            impl $name {
+                pub fn new<const I: usize>(
+                    value: &<<Self as $crate::variant::VariantAlternative<I>>::Type as $crate::element::Element>::Borrowed,
+                ) -> ::std::pin::Pin<Box<Self>>
+                where
+                    Self: $crate::variant::VariantAlternative<I>,
+                {
+                    let variant = Box::into_raw(Box::new(::std::mem::MaybeUninit::<Self>::uninit())) as *mut Self;
+                    unsafe {
+                        ::std::ptr::addr_of_mut!((*variant).index).write($crate::variant::RawIndex::MAX);
+                        let mut variant = Box::into_pin(Box::from_raw(variant));
+                        variant.as_mut().emplace::<I>(value);
+                        variant
+                    }
+                }
+
+                /// Returns the index of the active alternative, or `VARIANT_NPOS` if the
+                /// variant is valueless by exception.
+                pub fn index(&self) -> usize {
+                    if self.valueless_by_exception() {
+                        $crate::variant::VARIANT_NPOS
+                    } else {
+                        self.index as usize
+                    }
+                }
+
+                pub fn valueless_by_exception(&self) -> bool {
+                    self.index == $crate::variant::RawIndex::MAX
+                }
+
+                pub fn get<const I: usize>(&self) -> Option<&<Self as $crate::variant::VariantAlternative<I>>::Type>
+                where
+                    Self: $crate::variant::VariantAlternative<I>,
+                {
+                    if self.index() != I {
+                        return None;
+                    }
+                    unsafe { Some(&*(&self.storage as *const _ as *const _)) }
+                }
+
+                pub fn get_value_mut<const I: usize>(
+                    self: ::std::pin::Pin<&mut Self>,
+                ) -> Option<&mut <Self as $crate::variant::VariantAlternative<I>>::Type>
+                where
+                    Self: $crate::variant::VariantAlternative<I>,
+                {
+                    if self.index() != I {
+                        return None;
+                    }
+                    unsafe {
+                        let this = self.get_unchecked_mut();
+                        Some(&mut *(&mut this.storage as *mut _ as *mut _))
+                    }
+                }
+
+                /// Destroys the active alternative and constructs the `I`th one from `value`.
+                pub fn emplace<const I: usize>(
+                    self: ::std::pin::Pin<&mut Self>,
+                    value: &<<Self as $crate::variant::VariantAlternative<I>>::Type as $crate::element::Element>::Borrowed,
+                ) where
+                    Self: $crate::variant::VariantAlternative<I>,
+                {
+                    let this = unsafe { self.get_unchecked_mut() };
+                    this.reset();
+                    unsafe {
+                        $crate::variant::Alternative::construct(
+                            &mut this.storage as *mut _ as *mut <Self as $crate::variant::VariantAlternative<I>>::Type,
+                            value,
+                        );
+                    }
+                    this.index = I as $crate::variant::RawIndex;
+                }
+
+                /// Borrows the active alternative as an enum which can be matched.
+                pub fn as_enum(&self) -> $view<'_> {
+                    let storage = &self.storage as *const _;
+                    $(
+                        if self.index() == Index::$alternative as usize {
+                            return $view::$alternative(unsafe { &*(storage as *const $t) });
+                        }
+                    )+
+                    $view::ValuelessByException
+                }
+
+                /// Destroys the active alternative, leaving the variant valueless.
+                fn reset(&mut self) {
+                    let storage = &mut self.storage as *mut _;
+                    $(
+                        if self.index() == Index::$alternative as usize {
+                            unsafe { <$t as $crate::variant::Alternative>::destruct(storage as *mut $t) };
+                        }
+                    )+
+                    self.index = $crate::variant::RawIndex::MAX;
+                }
+            }
+
+            impl Drop for $name {
+                fn drop(&mut self) {
+                    self.reset();
+                }
+            }
+        };
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::VectorSlice;
+    use std::mem::{align_of, size_of};
+    use std::pin::Pin;
+
+    cpp_variant! {
+        /// `std::variant<int64_t, double, std::string, std::vector<uint8_t>>`.
+        pub struct Message: cpp_class!(
+            unsafe struct MessageLayout as "std::variant<int64_t, double, std::string, std::vector<uint8_t>>"
+        ),
+        pub enum MessageRef {
+            Int(i64),
+            Float(f64),
+            Text(StlString),
+            Bytes(VectorOfU8),
+        }
+    }
+
+    /// `checked`, whose constructor throws for negative values.
+    #[repr(C)]
+    struct Checked {
+        value: i32,
+    }
+
+    impl Element for Checked {
+        type Borrowed = i32;
+        type Owned = i32;
+
+        fn to_rust(&self) -> Self::Owned {
+            self.value
+        }
+
+        fn assign(&mut self, value: &Self::Borrowed) {
+            self.value = *value;
+        }
+    }
+
+    impl Alternative for Checked {
+        unsafe fn construct(ptr: *mut Self, value: &i32) {
+            ptr::write(ptr, Checked { value: *value });
+        }
+
+        unsafe fn destruct(_ptr: *mut Self) {}
+    }
+
+    cpp_variant! {
+        struct Checkpoint: cpp_class!(unsafe struct CheckpointLayout as "std::variant<int64_t, checked>"),
+        enum CheckpointRef {
+            Int(i64),
+            Checked(Checked),
+        }
+    }
+
+    #[repr(C)]
+    struct StructWithVariant {
+        tag: i32,
+        message: Message,
+        checkpoint: Checkpoint,
+    }
+
+    fn describe(message: &Message) -> String {
+        match message.as_enum() {
+            MessageRef::Int(value) => format!("int {}", value),
+            MessageRef::Float(value) => format!("float {}", value),
+            MessageRef::Text(text) => format!("text {}", text),
+            MessageRef::Bytes(bytes) => format!("bytes {:?}", bytes.as_slice()),
+            MessageRef::ValuelessByException => "valueless".to_owned(),
+        }
+    }
+
+    #[test]
+    fn unittest_variant() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithVariant as "struct_with_variant*" {
+                static struct_with_variant x{1, std::string("hello"), int64_t(3)};
+                return &x;
+            })
+        };
+
+        let (size, align) = unsafe {
+            let mut align = 0usize;
+            let size = cpp!([mut align as "size_t"] -> usize as "size_t" {
+                align = alignof(struct_with_variant);
+                return sizeof(struct_with_variant);
+            });
+            (size, align)
+        };
+        assert_eq!(size, size_of::<StructWithVariant>());
+        assert_eq!(align, align_of::<StructWithVariant>());
+
+        assert_eq!(x.message.index(), 2);
+        assert_eq!(x.message.get::<2>().unwrap().to_string(), "hello");
+        assert!(x.message.get::<0>().is_none());
+        assert_eq!(describe(&x.message), "text hello");
+
+        let mut message = unsafe { Pin::new_unchecked(&mut x.message) };
+        message.as_mut().emplace::<3>(&[1, 2, 3]);
+        assert_eq!(message.index(), 3);
+        message.get_value_mut::<3>().unwrap()[0] = 7;
+        assert_eq!(describe(&x.message), "bytes [7, 2, 3]");
+
+        let sum = unsafe {
+            let x: &StructWithVariant = x;
+            cpp!([x as "const struct_with_variant*"] -> size_t as "size_t" {
+                const auto& bytes = std::get<3>(x->message);
+                return x->message.index() + bytes[0] + bytes[1] + bytes[2];
+            })
+        };
+        assert_eq!(sum, 15);
+
+        unsafe { Pin::new_unchecked(&mut x.message) }.emplace::<1>(&0.5);
+        let value = unsafe {
+            let x: &StructWithVariant = x;
+            cpp!([x as "const struct_with_variant*"] -> f64 as "double" {
+                return std::get<double>(x->message);
+            })
+        };
+        assert_eq!(value, 0.5);
+    }
+
+    #[test]
+    fn unittest_variant_valueless() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithVariant as "struct_with_variant*" {
+                static struct_with_variant x{0, int64_t(0), checked(5)};
+                return &x;
+            })
+        };
+
+        assert_eq!(
+            x.checkpoint.get::<1>().map(|checked| checked.value),
+            Some(5)
+        );
+        unsafe { Pin::new_unchecked(&mut x.checkpoint) }.emplace::<0>(&9);
+
+        unsafe {
+            let x: &mut StructWithVariant = x;
+            cpp!([x as "struct_with_variant*"] {
+                try {
+                    x->checkpoint.emplace<1>(-1);
+                } catch (const std::invalid_argument&) {
+                }
+            })
+        };
+        assert!(x.checkpoint.valueless_by_exception());
+        assert_eq!(x.checkpoint.index(), VARIANT_NPOS);
+        assert!(x.checkpoint.get::<0>().is_none());
+        assert!(matches!(
+            x.checkpoint.as_enum(),
+            CheckpointRef::ValuelessByException
+        ));
+
+        unsafe { Pin::new_unchecked(&mut x.checkpoint) }.emplace::<1>(&7);
+        assert!(
+            matches!(x.checkpoint.as_enum(), CheckpointRef::Checked(checked) if checked.value == 7)
+        );
+        let value = unsafe {
+            let x: &StructWithVariant = x;
+            cpp!([x as "const struct_with_variant*"] -> i32 as "int32_t" {
+                return x->checkpoint.valueless_by_exception() ? -1 : std::get<checked>(x->checkpoint).value;
+            })
+        };
+        assert_eq!(value, 7);
+    }
+
+    #[test]
+    fn unittest_variant_new() {
+        let mut message = Message::new::<2>("boncheol");
+        assert_eq!(describe(&message), "text boncheol");
+        message.as_mut().emplace::<0>(&42);
+        assert_eq!(message.get::<0>(), Some(&42));
+        assert_eq!(describe(&message), "int 42");
+    }
+}
@@ -1,16 +1,26 @@
+use std::cmp;
 use std::ffi::c_void;
 use std::fmt;
+use std::iter::FromIterator;
 use std::marker::PhantomData;
+use std::mem::{self, MaybeUninit};
 use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::ptr;
 use std::slice;
 
 use libc::size_t;
 
 use crate::bindings::root::rust::*;
+use crate::element::Element;
 use crate::memory::UniquePtr;
+use crate::string::String as StlString;
+#[cfg(feature = "tuple")]
+use crate::tuple::Tuple3;
+use crate::utility::Pair;
 
 cpp! {{
     #include <memory>
+    #include <string>
     #include <vector>
 
     #include "wrapper.hpp"
@@ -38,6 +48,32 @@ cpp! {{
         std::vector<std::unique_ptr<std::string>> cities;
         std::vector<std::unique_ptr<subclass>> derived;
     };
+
+    struct struct_with_string_vectors {
+        std::vector<std::string> tags;
+    };
+
+    struct struct_with_nested_vectors {
+        std::vector<std::vector<float>> tensor;
+        std::vector<std::vector<int32_t>> adjacency;
+    };
+
+    struct point3f {
+        float x, y, z;
+    };
+
+    struct bbox {
+        point3f min, max;
+        int32_t label;
+    };
+
+    struct struct_with_pod_vectors {
+        std::vector<point3f> points;
+        std::vector<bbox> boxes;
+    };
+
+    static_assert(sizeof(std::vector<point3f>) == 3 * sizeof(void*), "");
+    static_assert(sizeof(std::vector<bbox>) == 3 * sizeof(void*), "");
 }}
 
 pub trait VectorSlice {
@@ -55,15 +91,26 @@ pub trait VectorSlice {
         self.as_slice().len()
     }
 
+    // `data()` of an empty vector may be null, which a slice must not be.
     fn as_slice(&self) -> &[Self::Item] {
-        unsafe { slice::from_raw_parts(self.get_ptr(), self.size()) }
+        match self.size() {
+            0 => &[],
+            size => unsafe { slice::from_raw_parts(self.get_ptr(), size) },
+        }
     }
 
     fn as_mut_slice(&mut self) -> &mut [Self::Item] {
-        unsafe { slice::from_raw_parts_mut(self.get_mut_ptr(), self.size()) }
+        match self.size() {
+            0 => &mut [],
+            size => unsafe { slice::from_raw_parts_mut(self.get_mut_ptr(), size) },
+        }
     }
 }
 
+/// Marks vectors whose elements may be moved byte by byte, e.g. by `<[T]>::swap`, which
+/// `VectorOfString` is not as libstdc++ strings point into themselves.
+pub trait VectorSliceMut: VectorSlice {}
+
 macro_rules! add_impl {
     ($($t:ty)*) => ($(
         impl fmt::Debug for $t
@@ -83,12 +130,6 @@ macro_rules! add_impl {
             }
         }
 
-        impl DerefMut for $t {
-            fn deref_mut(&mut self) -> &mut Self::Target {
-                self.as_mut_slice()
-            }
-        }
-
         impl Index<usize> for $t {
             type Output = <$t as VectorSlice>::Item;
 
@@ -97,12 +138,6 @@ macro_rules! add_impl {
             }
         }
 
-        impl IndexMut<usize> for $t {
-            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-                &mut self.as_mut_slice()[index]
-            }
-        }
-
         impl<'a> IntoIterator for &'a $t {
             type Item = &'a <$t as VectorSlice>::Item;
             type IntoIter = slice::Iter<'a, <$t as VectorSlice>::Item>;
@@ -111,6 +146,22 @@ macro_rules! add_impl {
                 self.iter()
             }
         }
+    )*)
+}
+
+macro_rules! add_mut_impl {
+    ($($t:ty)*) => ($(
+        impl DerefMut for $t {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                self.as_mut_slice()
+            }
+        }
+
+        impl IndexMut<usize> for $t {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                &mut self.as_mut_slice()[index]
+            }
+        }
 
         impl<'a> IntoIterator for &'a mut $t {
             type Item = &'a mut <$t as VectorSlice>::Item;
@@ -123,7 +174,13 @@ macro_rules! add_impl {
     )*)
 }
 
-add_impl!(VectorOfU8 VectorOfI32 VectorOfI64 VectorOfF32);
+add_impl!(VectorOfU8 VectorOfI32 VectorOfI64 VectorOfF32 VectorOfPairI32F32 VectorOfString);
+add_mut_impl!(VectorOfU8 VectorOfI32 VectorOfI64 VectorOfF32 VectorOfPairI32F32);
+
+#[cfg(feature = "tuple")]
+add_impl!(VectorOfTupleI32F32F64);
+#[cfg(feature = "tuple")]
+add_mut_impl!(VectorOfTupleI32F32F64);
 
 pub trait VectorRemove: VectorSlice {
     fn erase_range(&mut self, offset: usize, len: usize) {
@@ -194,24 +251,26 @@ impl VectorSlice for VectorOfU8 {
         }
     }
 
-    fn get_mut_ptr(&mut self) -> *mut Self::Item {
+    fn size(&self) -> usize {
         unsafe {
-            cpp!([self as "std::vector<uint8_t>*"]
-                  -> *mut u8 as "uint8_t*" {
-                return self->data();
+            cpp!([self as "const std::vector<uint8_t>*"] -> size_t as "size_t" {
+                return self->size();
             })
         }
     }
 
-    fn size(&self) -> usize {
+    fn get_mut_ptr(&mut self) -> *mut Self::Item {
         unsafe {
-            cpp!([self as "const std::vector<uint8_t>*"] -> size_t as "size_t" {
-                return self->size();
+            cpp!([self as "std::vector<uint8_t>*"]
+                  -> *mut u8 as "uint8_t*" {
+                return self->data();
             })
         }
     }
 }
 
+impl VectorSliceMut for VectorOfU8 {}
+
 impl VectorRemove for VectorOfU8 {
     fn erase_range(&mut self, offset: usize, size: usize) {
         let begin = offset as size_t;
@@ -234,6 +293,25 @@ impl VectorInsert<u8> for VectorOfU8 {
     }
 }
 
+impl Element for VectorOfU8 {
+    type Borrowed = [u8];
+    type Owned = Vec<u8>;
+
+    fn to_rust(&self) -> Self::Owned {
+        self.as_slice().to_vec()
+    }
+
+    fn assign(&mut self, value: &Self::Borrowed) {
+        let ptr = value.as_ptr();
+        let len = value.len() as size_t;
+        unsafe {
+            cpp!([self as "std::vector<uint8_t>*", ptr as "const uint8_t*", len as "size_t"] {
+                self->assign(ptr, ptr + len);
+            })
+        }
+    }
+}
+
 #[repr(C)]
 pub struct VectorOfI32(vector_of_int32_t);
 
@@ -249,24 +327,26 @@ impl VectorSlice for VectorOfI32 {
         }
     }
 
-    fn get_mut_ptr(&mut self) -> *mut Self::Item {
+    fn size(&self) -> usize {
         unsafe {
-            cpp!([self as "std::vector<int32_t>*"]
-                  -> *mut i32 as "int32_t*" {
-                return self->data();
+            cpp!([self as "const std::vector<int32_t>*"] -> size_t as "size_t" {
+                return self->size();
             })
         }
     }
 
-    fn size(&self) -> usize {
+    fn get_mut_ptr(&mut self) -> *mut Self::Item {
         unsafe {
-            cpp!([self as "const std::vector<int32_t>*"] -> size_t as "size_t" {
-                return self->size();
+            cpp!([self as "std::vector<int32_t>*"]
+                  -> *mut i32 as "int32_t*" {
+                return self->data();
             })
         }
     }
 }
 
+impl VectorSliceMut for VectorOfI32 {}
+
 impl VectorRemove for VectorOfI32 {
     fn erase_range(&mut self, offset: usize, size: usize) {
         let begin = offset as size_t;
@@ -304,24 +384,26 @@ impl VectorSlice for VectorOfI64 {
         }
     }
 
-    fn get_mut_ptr(&mut self) -> *mut Self::Item {
+    fn size(&self) -> usize {
         unsafe {
-            cpp!([self as "std::vector<int64_t>*"]
-                  -> *mut i64 as "int64_t*" {
-                return self->data();
+            cpp!([self as "const std::vector<int64_t>*"] -> size_t as "size_t" {
+                return self->size();
             })
         }
     }
 
-    fn size(&self) -> usize {
+    fn get_mut_ptr(&mut self) -> *mut Self::Item {
         unsafe {
-            cpp!([self as "const std::vector<int64_t>*"] -> size_t as "size_t" {
-                return self->size();
+            cpp!([self as "std::vector<int64_t>*"]
+                  -> *mut i64 as "int64_t*" {
+                return self->data();
             })
         }
     }
 }
 
+impl VectorSliceMut for VectorOfI64 {}
+
 impl VectorRemove for VectorOfI64 {
     fn erase_range(&mut self, offset: usize, size: usize) {
         let begin = offset as size_t;
@@ -345,186 +427,1040 @@ impl VectorInsert<i64> for VectorOfI64 {
 }
 
 #[repr(C)]
-pub struct VectorOfF32(vector_of_float);
+pub struct VectorOfPairI32F32(vector_of_pair_of_int32_t_float);
 
-impl VectorSlice for VectorOfF32 {
-    type Item = f32;
+impl VectorSlice for VectorOfPairI32F32 {
+    type Item = Pair<i32, f32>;
 
     fn get_ptr(&self) -> *const Self::Item {
         unsafe {
-            cpp!([self as "const std::vector<float>*"]
-                  -> *const f32 as "const float*" {
+            cpp!([self as "const std::vector<std::pair<int32_t, float>>*"]
+                  -> *const Pair<i32, f32> as "const std::pair<int32_t, float>*" {
                 return self->data();
             })
         }
     }
 
-    fn get_mut_ptr(&mut self) -> *mut Self::Item {
+    fn size(&self) -> usize {
         unsafe {
-            cpp!([self as "std::vector<float>*"]
-                  -> *mut f32 as "float*" {
-                return self->data();
+            cpp!([self as "const std::vector<std::pair<int32_t, float>>*"] -> size_t as "size_t" {
+                return self->size();
             })
         }
     }
 
-    fn size(&self) -> usize {
+    fn get_mut_ptr(&mut self) -> *mut Self::Item {
         unsafe {
-            cpp!([self as "const std::vector<float>*"] -> size_t as "size_t" {
-                return self->size();
+            cpp!([self as "std::vector<std::pair<int32_t, float>>*"]
+                  -> *mut Pair<i32, f32> as "std::pair<int32_t, float>*" {
+                return self->data();
             })
         }
     }
 }
 
-impl VectorRemove for VectorOfF32 {
+impl VectorSliceMut for VectorOfPairI32F32 {}
+
+impl VectorRemove for VectorOfPairI32F32 {
     fn erase_range(&mut self, offset: usize, size: usize) {
         let begin = offset as size_t;
         let end = offset + size as size_t;
         unsafe {
-            cpp!([self as "std::vector<float>*", begin as "size_t", end as "size_t"] {
+            cpp!([self as "std::vector<std::pair<int32_t, float>>*", begin as "size_t", end as "size_t"] {
                 self->erase(self->begin() + begin, self->begin() + end);
             });
         }
     }
 }
 
-impl VectorInsert<f32> for VectorOfF32 {
+impl VectorInsert<Pair<i32, f32>> for VectorOfPairI32F32 {
     fn push_back(&mut self, v: Self::Item) {
         unsafe {
-            cpp!([self as "std::vector<float>*", v as "float"] {
+            cpp!([self as "std::vector<std::pair<int32_t, float>>*", v as "std::pair<int32_t, float>"] {
                 self->push_back(v);
             })
         }
     }
 }
 
-pub struct Iter<'a, T> {
-    vector: &'a VectorOfUniquePtr<T>,
-    index: usize,
+/// `std::vector<std::tuple<int32_t, float, double>>`.
+#[cfg(feature = "tuple")]
+#[repr(C)]
+pub struct VectorOfTupleI32F32F64(vector_of_tuple_of_int32_t_float_double);
+
+#[cfg(feature = "tuple")]
+impl VectorSlice for VectorOfTupleI32F32F64 {
+    type Item = Tuple3<i32, f32, f64>;
+
+    fn get_ptr(&self) -> *const Self::Item {
+        unsafe {
+            cpp!([self as "const std::vector<std::tuple<int32_t, float, double>>*"]
+                  -> *const Tuple3<i32, f32, f64> as "const std::tuple<int32_t, float, double>*" {
+                return self->data();
+            })
+        }
+    }
+
+    fn size(&self) -> usize {
+        unsafe {
+            cpp!([self as "const std::vector<std::tuple<int32_t, float, double>>*"] -> size_t as "size_t" {
+                return self->size();
+            })
+        }
+    }
+
+    fn get_mut_ptr(&mut self) -> *mut Self::Item {
+        unsafe {
+            cpp!([self as "std::vector<std::tuple<int32_t, float, double>>*"]
+                  -> *mut Tuple3<i32, f32, f64> as "std::tuple<int32_t, float, double>*" {
+                return self->data();
+            })
+        }
+    }
 }
 
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
+#[cfg(feature = "tuple")]
+impl VectorSliceMut for VectorOfTupleI32F32F64 {}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index == self.vector.size() {
-            return None;
+#[cfg(feature = "tuple")]
+impl VectorRemove for VectorOfTupleI32F32F64 {
+    fn erase_range(&mut self, offset: usize, size: usize) {
+        let begin = offset as size_t;
+        let end = offset + size as size_t;
+        unsafe {
+            cpp!([self as "std::vector<std::tuple<int32_t, float, double>>*", begin as "size_t", end as "size_t"] {
+                self->erase(self->begin() + begin, self->begin() + end);
+            });
         }
+    }
+}
 
-        self.index += 1;
-        Some(&self.vector[self.index - 1])
+#[cfg(feature = "tuple")]
+impl VectorInsert<Tuple3<i32, f32, f64>> for VectorOfTupleI32F32F64 {
+    fn push_back(&mut self, v: Self::Item) {
+        unsafe {
+            cpp!([self as "std::vector<std::tuple<int32_t, float, double>>*", v as "std::tuple<int32_t, float, double>"] {
+                self->push_back(v);
+            })
+        }
     }
 }
 
 #[repr(C)]
-pub struct VectorOfUniquePtr<T>(vector_of_dummy_unique_ptr, PhantomData<T>);
+pub struct VectorOfF32(vector_of_float);
 
-impl<T> VectorSlice for VectorOfUniquePtr<T> {
-    type Item = UniquePtr<T>;
+impl VectorSlice for VectorOfF32 {
+    type Item = f32;
 
     fn get_ptr(&self) -> *const Self::Item {
         unsafe {
-            cpp!([self as "const std::vector<dummy_unique_ptr>*"]
-                  -> *const c_void as "const dummy_unique_ptr*" {
+            cpp!([self as "const std::vector<float>*"]
+                  -> *const f32 as "const float*" {
                 return self->data();
-            }) as *const Self::Item
+            })
         }
     }
 
-    fn get_mut_ptr(&mut self) -> *mut Self::Item {
+    fn size(&self) -> usize {
         unsafe {
-            cpp!([self as "std::vector<dummy_unique_ptr>*"]
-                  -> *mut c_void as "dummy_unique_ptr*" {
-                return self->data();
-            }) as *mut Self::Item
+            cpp!([self as "const std::vector<float>*"] -> size_t as "size_t" {
+                return self->size();
+            })
         }
     }
 
-    fn size(&self) -> usize {
+    fn get_mut_ptr(&mut self) -> *mut Self::Item {
         unsafe {
-            cpp!([self as "const std::vector<dummy_unique_ptr>*"] -> size_t as "size_t" {
-                return self->size();
+            cpp!([self as "std::vector<float>*"]
+                  -> *mut f32 as "float*" {
+                return self->data();
             })
         }
     }
 }
 
-impl<T> Index<usize> for VectorOfUniquePtr<T> {
-    type Output = T;
+impl VectorSliceMut for VectorOfF32 {}
 
-    fn index(&self, index: usize) -> &Self::Output {
-        let index = index as size_t;
+impl VectorRemove for VectorOfF32 {
+    fn erase_range(&mut self, offset: usize, size: usize) {
+        let begin = offset as size_t;
+        let end = offset + size as size_t;
         unsafe {
-            let ptr = cpp!([self as "const std::vector<std::unique_ptr<void>>*", index as "size_t"]
-                  -> *const c_void as "const void*" {
-                return (*self)[index].get();
-            }) as *const Self::Output;
-
-            ptr.as_ref().unwrap()
+            cpp!([self as "std::vector<float>*", begin as "size_t", end as "size_t"] {
+                self->erase(self->begin() + begin, self->begin() + end);
+            });
         }
     }
 }
 
-impl<T> IndexMut<usize> for VectorOfUniquePtr<T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        let index = index as size_t;
+impl VectorInsert<f32> for VectorOfF32 {
+    fn push_back(&mut self, v: Self::Item) {
         unsafe {
-            let ptr = cpp!([self as "std::vector<std::unique_ptr<void>>*", index as "size_t"]
-                  -> *mut c_void as "void*" {
-                return (*self)[index].get();
-            }) as *mut Self::Output;
-
-            ptr.as_mut().unwrap()
+            cpp!([self as "std::vector<float>*", v as "float"] {
+                self->push_back(v);
+            })
         }
     }
 }
 
-impl<T> fmt::Debug for VectorOfUniquePtr<T>
-where
-    T: fmt::Debug,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list().entries(self.iter()).finish()
+/// `std::vector<std::string>`, whose elements are exposed as `string::String` in place.
+///
+/// It derefs to `&[StlString]` only, as the elements must not be moved byte by byte, and
+/// is mutated element by element with `set`, or with `swap` and `sort` in C++.
+#[repr(C)]
+pub struct VectorOfString(vector_of_string);
+
+impl VectorOfString {
+    pub fn new() -> Box<Self> {
+        let vector = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            cpp!([vector as "std::vector<std::string>*"] {
+                new (vector) std::vector<std::string>();
+            });
+            Box::from_raw(vector)
+        }
     }
-}
 
-impl<'a, T> IntoIterator for &'a VectorOfUniquePtr<T> {
-    type Item = &'a T;
-    type IntoIter = Iter<'a, T>;
+    /// Inserts `value` at `index`, shifting the following elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: &str) {
+        assert!(index <= self.size(), "insertion index out of bounds");
+        let index = index as size_t;
+        let ptr = value.as_ptr();
+        let len = value.len() as size_t;
+        unsafe {
+            cpp!([self as "std::vector<std::string>*", index as "size_t", ptr as "const char*", len as "size_t"] {
+                self->emplace(self->begin() + index, ptr, len);
+            })
+        }
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
+    /// Removes the element at `index` and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> std::string::String {
+        let value = self[index].to_rust();
+        self.erase(index);
+        value
+    }
+
+    /// Concatenates the elements, placing `separator` between each pair.
+    pub fn join(&self, separator: &str) -> std::string::String {
         self.iter()
+            .map(StlString::to_rust)
+            .collect::<Vec<_>>()
+            .join(separator)
     }
-}
 
-impl<T> VectorOfUniquePtr<T> {
-    pub fn iter(&self) -> Iter<T> {
-        Iter {
-            vector: self,
-            index: 0,
+    /// Assigns `value` to the element at `index` in C++.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn set(&mut self, index: usize, value: &str) {
+        assert!(index < self.size(), "index out of bounds");
+        let index = index as size_t;
+        let ptr = value.as_ptr();
+        let len = value.len() as size_t;
+        unsafe {
+            cpp!([self as "std::vector<std::string>*", index as "size_t", ptr as "const char*", len as "size_t"] {
+                (*self)[index].assign(ptr, len);
+            })
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
+    /// Swaps the elements at `a` and `b` with `std::swap`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let size = self.size();
+        assert!(a < size && b < size, "swap index out of bounds");
+        let a = a as size_t;
+        let b = b as size_t;
+        unsafe {
+            cpp!([self as "std::vector<std::string>*", a as "size_t", b as "size_t"] {
+                std::swap((*self)[a], (*self)[b]);
+            })
+        }
+    }
+
+    /// Sorts the elements in ascending order with `std::sort`.
+    pub fn sort(&mut self) {
+        unsafe {
+            cpp!([self as "std::vector<std::string>*"] {
+                std::sort(self->begin(), self->end());
+            })
+        }
+    }
+
+    /// Reverses the order of the elements with `std::reverse`.
+    pub fn reverse(&mut self) {
+        unsafe {
+            cpp!([self as "std::vector<std::string>*"] {
+                std::reverse(self->begin(), self->end());
+            })
+        }
+    }
+}
+
+impl VectorSlice for VectorOfString {
+    type Item = StlString;
+
+    fn get_ptr(&self) -> *const Self::Item {
+        unsafe {
+            cpp!([self as "const std::vector<std::string>*"]
+                  -> *const StlString as "const std::string*" {
+                return self->data();
+            })
+        }
+    }
+
+    fn size(&self) -> usize {
+        unsafe {
+            cpp!([self as "const std::vector<std::string>*"] -> size_t as "size_t" {
+                return self->size();
+            })
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Always panics, as the elements must not be moved byte by byte; use `set`, `swap` or
+    /// `sort` instead.
+    fn get_mut_ptr(&mut self) -> *mut Self::Item {
+        panic!("elements of VectorOfString are not mutable in place")
+    }
+
+    /// # Panics
+    ///
+    /// Always panics, see `get_mut_ptr`.
+    fn as_mut_slice(&mut self) -> &mut [Self::Item] {
+        panic!("elements of VectorOfString are not mutable in place")
+    }
+}
+
+impl VectorRemove for VectorOfString {
+    fn erase_range(&mut self, offset: usize, size: usize) {
+        let begin = offset as size_t;
+        let end = offset + size as size_t;
+        unsafe {
+            cpp!([self as "std::vector<std::string>*", begin as "size_t", end as "size_t"] {
+                self->erase(self->begin() + begin, self->begin() + end);
+            });
+        }
+    }
+}
+
+impl<'a> VectorInsert<&'a str> for VectorOfString {
+    fn push_back(&mut self, v: &'a str) {
+        let ptr = v.as_ptr();
+        let len = v.len() as size_t;
+        unsafe {
+            cpp!([self as "std::vector<std::string>*", ptr as "const char*", len as "size_t"] {
+                self->emplace_back(ptr, len);
+            })
+        }
+    }
+}
+
+impl Drop for VectorOfString {
+    fn drop(&mut self) {
+        unsafe {
+            cpp!([self as "std::vector<std::string>*"] {
+                self->~vector();
+            })
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Box<VectorOfString> {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut vector = VectorOfString::new();
+        for value in iter {
+            vector.push_back(value);
+        }
+        vector
+    }
+}
+
+impl From<Vec<std::string::String>> for Box<VectorOfString> {
+    fn from(values: Vec<std::string::String>) -> Self {
+        values.iter().map(std::string::String::as_str).collect()
+    }
+}
+
+impl From<&VectorOfString> for Vec<std::string::String> {
+    fn from(vector: &VectorOfString) -> Self {
+        vector.iter().map(StlString::to_rust).collect()
+    }
+}
+
+/// Vector types which can be elements of `std::vector<std::vector<T>>`, for which the
+/// instantiation exists in C++.
+pub trait NestedVector: VectorSlice + Sized {
+    /// Constructs an empty vector at `vector`.
+    ///
+    /// # Safety
+    ///
+    /// `vector` must point to uninitialized memory, which is overwritten without being dropped.
+    unsafe fn construct(vector: *mut VectorOfVector<Self>);
+
+    /// # Safety
+    ///
+    /// `vector` must not be used after this call.
+    unsafe fn destruct(vector: &mut VectorOfVector<Self>);
+
+    fn data(vector: &VectorOfVector<Self>) -> *const Self;
+
+    fn size(vector: &VectorOfVector<Self>) -> usize;
+
+    /// Inserts a copy of `values` as an inner vector at `index`.
+    fn insert(vector: &mut VectorOfVector<Self>, index: usize, values: &[Self::Item]);
+
+    fn erase_range(vector: &mut VectorOfVector<Self>, offset: usize, len: usize);
+}
+
+/// `std::vector<std::vector<T>>`, which derefs to a slice of inner vectors such as
+/// `[VectorOfF32]`.
+#[repr(C)]
+pub struct VectorOfVector<V: NestedVector>(vector_of_dummy_vector, PhantomData<V>);
+
+impl<V: NestedVector> VectorOfVector<V> {
+    pub fn new() -> Box<Self> {
+        let vector = Box::into_raw(Box::new(MaybeUninit::<Self>::uninit())) as *mut Self;
+        unsafe {
+            V::construct(vector);
+            Box::from_raw(vector)
+        }
+    }
+
+    /// Inserts a copy of `values` as an inner vector at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, values: &[V::Item]) {
+        assert!(index <= self.size(), "insertion index out of bounds");
+        V::insert(self, index, values);
+    }
+
+    /// Removes the inner vector at `index` and returns its elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> Vec<V::Item>
+    where
+        V::Item: Clone,
+    {
+        let values = self[index].as_slice().to_vec();
+        self.erase(index);
+        values
+    }
+
+    /// Copies the elements into one contiguous buffer, and returns it with the offsets of
+    /// the inner vectors, the last of which is the length of the buffer.
+    pub fn flatten(&self) -> (Vec<V::Item>, Vec<usize>)
+    where
+        V::Item: Clone,
+    {
+        let mut values = Vec::new();
+        let mut offsets = Vec::with_capacity(self.size() + 1);
+        offsets.push(0);
+        for inner in self.as_slice() {
+            values.extend_from_slice(inner.as_slice());
+            offsets.push(values.len());
+        }
+        (values, offsets)
+    }
+}
+
+impl<V: NestedVector> VectorSlice for VectorOfVector<V> {
+    type Item = V;
+
+    fn get_ptr(&self) -> *const Self::Item {
+        V::data(self)
+    }
+
+    fn size(&self) -> usize {
+        <V as NestedVector>::size(self)
+    }
+
+    fn get_mut_ptr(&mut self) -> *mut Self::Item {
+        V::data(self) as *mut V
+    }
+}
+
+impl<V: NestedVector> VectorSliceMut for VectorOfVector<V> {}
+
+impl<V: NestedVector> VectorRemove for VectorOfVector<V> {
+    fn erase_range(&mut self, offset: usize, len: usize) {
+        V::erase_range(self, offset, len);
+    }
+}
+
+impl<'a, V: NestedVector> VectorInsert<&'a [V::Item]> for VectorOfVector<V> {
+    fn push_back(&mut self, v: &'a [V::Item]) {
+        let index = self.size();
+        V::insert(self, index, v);
+    }
+}
+
+impl<V: NestedVector> Drop for VectorOfVector<V> {
+    fn drop(&mut self) {
+        unsafe { V::destruct(self) }
+    }
+}
+
+impl<V: NestedVector> Deref for VectorOfVector<V> {
+    type Target = [V];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<V: NestedVector> DerefMut for VectorOfVector<V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<V: NestedVector + fmt::Debug> fmt::Debug for VectorOfVector<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice().iter()).finish()
+    }
+}
+
+impl<'a, V: NestedVector> FromIterator<&'a [V::Item]> for Box<VectorOfVector<V>> {
+    fn from_iter<I: IntoIterator<Item = &'a [V::Item]>>(iter: I) -> Self {
+        let mut vector = VectorOfVector::new();
+        for values in iter {
+            vector.push_back(values);
+        }
+        vector
+    }
+}
+
+impl<V: NestedVector> From<Vec<Vec<V::Item>>> for Box<VectorOfVector<V>> {
+    fn from(values: Vec<Vec<V::Item>>) -> Self {
+        values.iter().map(Vec::as_slice).collect()
+    }
+}
+
+impl<V> From<&VectorOfVector<V>> for Vec<Vec<V::Item>>
+where
+    V: NestedVector,
+    V::Item: Clone,
+{
+    fn from(vector: &VectorOfVector<V>) -> Self {
+        vector
+            .as_slice()
+            .iter()
+            .map(|inner| inner.as_slice().to_vec())
+            .collect()
+    }
+}
+
+impl NestedVector for VectorOfI32 {
+    unsafe fn construct(vector: *mut VectorOfVector<Self>) {
+        cpp!([vector as "std::vector<std::vector<int32_t>>*"] {
+            new (vector) std::vector<std::vector<int32_t>>();
+        })
+    }
+
+    unsafe fn destruct(vector: &mut VectorOfVector<Self>) {
+        cpp!([vector as "std::vector<std::vector<int32_t>>*"] {
+            vector->~vector();
+        })
+    }
+
+    fn data(vector: &VectorOfVector<Self>) -> *const Self {
+        unsafe {
+            cpp!([vector as "const std::vector<std::vector<int32_t>>*"]
+                  -> *const VectorOfI32 as "const std::vector<int32_t>*" {
+                return vector->data();
+            })
+        }
+    }
+
+    fn size(vector: &VectorOfVector<Self>) -> usize {
+        unsafe {
+            cpp!([vector as "const std::vector<std::vector<int32_t>>*"] -> size_t as "size_t" {
+                return vector->size();
+            })
+        }
+    }
+
+    fn insert(vector: &mut VectorOfVector<Self>, index: usize, values: &[i32]) {
+        let index = index as size_t;
+        let ptr = values.as_ptr();
+        let len = values.len() as size_t;
+        unsafe {
+            cpp!([vector as "std::vector<std::vector<int32_t>>*", index as "size_t",
+                  ptr as "const int32_t*", len as "size_t"] {
+                vector->emplace(vector->begin() + index, ptr, ptr + len);
+            })
+        }
+    }
+
+    fn erase_range(vector: &mut VectorOfVector<Self>, offset: usize, len: usize) {
+        let begin = offset as size_t;
+        let end = offset + len as size_t;
+        unsafe {
+            cpp!([vector as "std::vector<std::vector<int32_t>>*", begin as "size_t", end as "size_t"] {
+                vector->erase(vector->begin() + begin, vector->begin() + end);
+            })
+        }
+    }
+}
+
+impl NestedVector for VectorOfF32 {
+    unsafe fn construct(vector: *mut VectorOfVector<Self>) {
+        cpp!([vector as "std::vector<std::vector<float>>*"] {
+            new (vector) std::vector<std::vector<float>>();
+        })
+    }
+
+    unsafe fn destruct(vector: &mut VectorOfVector<Self>) {
+        cpp!([vector as "std::vector<std::vector<float>>*"] {
+            vector->~vector();
+        })
+    }
+
+    fn data(vector: &VectorOfVector<Self>) -> *const Self {
+        unsafe {
+            cpp!([vector as "const std::vector<std::vector<float>>*"]
+                  -> *const VectorOfF32 as "const std::vector<float>*" {
+                return vector->data();
+            })
+        }
+    }
+
+    fn size(vector: &VectorOfVector<Self>) -> usize {
+        unsafe {
+            cpp!([vector as "const std::vector<std::vector<float>>*"] -> size_t as "size_t" {
+                return vector->size();
+            })
+        }
+    }
+
+    fn insert(vector: &mut VectorOfVector<Self>, index: usize, values: &[f32]) {
+        let index = index as size_t;
+        let ptr = values.as_ptr();
+        let len = values.len() as size_t;
+        unsafe {
+            cpp!([vector as "std::vector<std::vector<float>>*", index as "size_t",
+                  ptr as "const float*", len as "size_t"] {
+                vector->emplace(vector->begin() + index, ptr, ptr + len);
+            })
+        }
+    }
+
+    fn erase_range(vector: &mut VectorOfVector<Self>, offset: usize, len: usize) {
+        let begin = offset as size_t;
+        let end = offset + len as size_t;
+        unsafe {
+            cpp!([vector as "std::vector<std::vector<float>>*", begin as "size_t", end as "size_t"] {
+                vector->erase(vector->begin() + begin, vector->begin() + end);
+            })
+        }
+    }
+}
+
+/// Trivially copyable element types of `VectorOf<T>`, declared with `cpp_vector_of!`.
+///
+/// # Safety
+///
+/// `Self` must be `#[repr(C)]` and have the same layout as its C++ counterpart.
+pub unsafe trait PodElement: Copy {}
+
+/// `std::vector<T>` of a trivially copyable `T`, which is manipulated from Rust through
+/// the begin, end and end of storage pointers both libstdc++ and libc++ store.
+///
+/// The storage is allocated and freed by `::operator new` and `::operator delete` as
+/// `std::allocator<T>` does, so either language can grow or destroy the vector.
+#[repr(C)]
+pub struct VectorOf<T: PodElement> {
+    begin: *mut T,
+    end: *mut T,
+    end_of_storage: *mut T,
+}
+
+impl<T: PodElement> VectorOf<T> {
+    pub fn new() -> Box<Self> {
+        Box::new(VectorOf {
+            begin: ptr::null_mut(),
+            end: ptr::null_mut(),
+            end_of_storage: ptr::null_mut(),
+        })
+    }
+
+    pub fn capacity(&self) -> usize {
+        (self.end_of_storage as usize - self.begin as usize) / mem::size_of::<T>()
+    }
+
+    /// Reserves capacity for at least `additional` more elements, doubling the capacity
+    /// as libstdc++ does when it grows.
+    pub fn reserve(&mut self, additional: usize) {
+        let len = self.size();
+        if self.capacity() - len >= additional {
+            return;
+        }
+
+        let capacity = cmp::max(len * 2, len + additional);
+        unsafe {
+            let begin = allocate(capacity * mem::size_of::<T>(), mem::align_of::<T>()) as *mut T;
+            if !self.begin.is_null() {
+                ptr::copy_nonoverlapping(self.begin, begin, len);
+                deallocate(self.begin as *mut c_void, mem::align_of::<T>());
+            }
+            self.begin = begin;
+            self.end = begin.add(len);
+            self.end_of_storage = begin.add(capacity);
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        let len = self.size();
+        assert!(index <= len, "insertion index out of bounds");
+        self.reserve(1);
+        unsafe {
+            let ptr = self.begin.add(index);
+            ptr::copy(ptr, ptr.add(1), len - index);
+            ptr.write(value);
+            self.end = self.end.add(1);
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> T {
+        let value = self[index];
+        self.erase(index);
+        value
+    }
+}
+
+impl<T: PodElement> VectorSlice for VectorOf<T> {
+    type Item = T;
+
+    fn get_ptr(&self) -> *const Self::Item {
+        self.begin
+    }
+
+    fn size(&self) -> usize {
+        (self.end as usize - self.begin as usize) / mem::size_of::<T>()
+    }
+
+    fn get_mut_ptr(&mut self) -> *mut Self::Item {
+        self.begin
+    }
+}
+
+impl<T: PodElement> VectorSliceMut for VectorOf<T> {}
+
+impl<T: PodElement> VectorRemove for VectorOf<T> {
+    fn erase_range(&mut self, offset: usize, len: usize) {
+        let size = self.size();
+        assert!(offset + len <= size, "erase range out of bounds");
+        unsafe {
+            let ptr = self.begin.add(offset);
+            ptr::copy(ptr.add(len), ptr, size - offset - len);
+            self.end = self.end.sub(len);
+        }
+    }
+}
+
+impl<T: PodElement> VectorInsert<T> for VectorOf<T> {
+    fn push_back(&mut self, v: T) {
+        let index = self.size();
+        self.insert(index, v);
+    }
+}
+
+impl<T: PodElement> Drop for VectorOf<T> {
+    fn drop(&mut self) {
+        if !self.begin.is_null() {
+            unsafe { deallocate(self.begin as *mut c_void, mem::align_of::<T>()) }
+        }
+    }
+}
+
+impl<T: PodElement> Deref for VectorOf<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: PodElement> DerefMut for VectorOf<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: PodElement + fmt::Debug> fmt::Debug for VectorOf<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice().iter()).finish()
+    }
+}
+
+impl<T: PodElement> Extend<T> for VectorOf<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T: PodElement> FromIterator<T> for Box<VectorOf<T>> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vector = VectorOf::new();
+        vector.extend(iter);
+        vector
+    }
+}
+
+impl<T: PodElement> From<&[T]> for Box<VectorOf<T>> {
+    fn from(values: &[T]) -> Self {
+        values.iter().copied().collect()
+    }
+}
+
+impl<T: PodElement> From<&VectorOf<T>> for Vec<T> {
+    fn from(vector: &VectorOf<T>) -> Self {
+        vector.as_slice().to_vec()
+    }
+}
+
+cpp! {{
+    #include <algorithm>
+    #include <new>
+
+    // Allocate as `std::allocator<T>` does for trivially copyable `T`.
+    static void* allocate_elements(size_t bytes, size_t align) {
+    #if __cpp_aligned_new
+        if (align > __STDCPP_DEFAULT_NEW_ALIGNMENT__) {
+            return ::operator new(bytes, std::align_val_t(align));
+        }
+    #endif
+        (void)align;
+        return ::operator new(bytes);
+    }
+
+    static void deallocate_elements(void* ptr, size_t align) {
+    #if __cpp_aligned_new
+        if (align > __STDCPP_DEFAULT_NEW_ALIGNMENT__) {
+            ::operator delete(ptr, std::align_val_t(align));
+            return;
+        }
+    #endif
+        (void)align;
+        ::operator delete(ptr);
+    }
+}}
+
+unsafe fn allocate(bytes: usize, align: usize) -> *mut c_void {
+    let bytes = bytes as size_t;
+    let align = align as size_t;
+    cpp!([bytes as "size_t", align as "size_t"] -> *mut c_void as "void*" {
+        return allocate_elements(bytes, align);
+    })
+}
+
+unsafe fn deallocate(ptr: *mut c_void, align: usize) {
+    let align = align as size_t;
+    cpp!([ptr as "void*", align as "size_t"] {
+        deallocate_elements(ptr, align);
+    })
+}
+
+/// Declares trivially copyable `#[repr(C)]` types as elements of `VectorOf<T>`.
+///
+/// Each type is paired with a `cpp_class!` naming its C++ counterpart, which `cpp_build`
+/// only finds when it is written out in the source. The build fails unless the C++ type
+/// is trivially copyable and has the size and alignment of the Rust type.
+///
+/// ```ignore
+/// cpp_vector_of! {
+///     Point3f: cpp_class!(unsafe struct Point3fLayout as "Point3f");
+///     BBox: cpp_class!(unsafe struct BBoxLayout as "BBox");
+/// }
+/// ```
+#[macro_export]
+macro_rules! cpp_vector_of {
+    ($($t:ty: $class:ident!(unsafe struct $layout:ident as $cpp:literal);)+) => {$(
+        $class!(unsafe struct $layout as $cpp);
+
+        const _: () = {
+            fn assert_trivially_copyable<T: Copy>() {}
+            let _ = assert_trivially_copyable::<$layout>;
+
+            assert!(
+                ::std::mem::size_of::<$t>() == ::std::mem::size_of::<$layout>(),
+                concat!("size of `", stringify!($t), "` differs from sizeof(", $cpp, ")")
+            );
+            assert!(
+                ::std::mem::align_of::<$t>() == ::std::mem::align_of::<$layout>(),
+                concat!("alignment of `", stringify!($t), "` differs from alignof(", $cpp, ")")
+            );
+        };
+
+        unsafe impl $crate::vector::PodElement for $t {}
+    )+};
+}
+
+pub struct Iter<'a, T> {
+    vector: &'a VectorOfUniquePtr<T>,
+    index: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.vector.size() {
+            return None;
+        }
+
+        self.index += 1;
+        Some(&self.vector[self.index - 1])
+    }
+}
+
+#[repr(C)]
+pub struct VectorOfUniquePtr<T>(vector_of_dummy_unique_ptr, PhantomData<T>);
+
+impl<T> VectorSlice for VectorOfUniquePtr<T> {
+    type Item = UniquePtr<T>;
+
+    fn get_ptr(&self) -> *const Self::Item {
+        unsafe {
+            cpp!([self as "const std::vector<dummy_unique_ptr>*"]
+                  -> *const c_void as "const dummy_unique_ptr*" {
+                return self->data();
+            }) as *const Self::Item
+        }
+    }
+
+    fn size(&self) -> usize {
+        unsafe {
+            cpp!([self as "const std::vector<dummy_unique_ptr>*"] -> size_t as "size_t" {
+                return self->size();
+            })
+        }
+    }
+
+    fn get_mut_ptr(&mut self) -> *mut Self::Item {
+        unsafe {
+            cpp!([self as "std::vector<dummy_unique_ptr>*"]
+                  -> *mut c_void as "dummy_unique_ptr*" {
+                return self->data();
+            }) as *mut Self::Item
+        }
+    }
+}
+
+impl<T> VectorSliceMut for VectorOfUniquePtr<T> {}
+
+impl<T> Index<usize> for VectorOfUniquePtr<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        let index = index as size_t;
+        unsafe {
+            let ptr = cpp!([self as "const std::vector<std::unique_ptr<void>>*", index as "size_t"]
+                  -> *const c_void as "const void*" {
+                return (*self)[index].get();
+            }) as *const Self::Output;
+
+            ptr.as_ref().unwrap()
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for VectorOfUniquePtr<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let index = index as size_t;
+        unsafe {
+            let ptr = cpp!([self as "std::vector<std::unique_ptr<void>>*", index as "size_t"]
+                  -> *mut c_void as "void*" {
+                return (*self)[index].get();
+            }) as *mut Self::Output;
+
+            ptr.as_mut().unwrap()
+        }
+    }
+}
+
+impl<T> fmt::Debug for VectorOfUniquePtr<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a VectorOfUniquePtr<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> VectorOfUniquePtr<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            vector: self,
+            index: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
     use super::*;
-    use crate::string::String as StlString;
+    use crate::mirror::CppMirror;
 
+    #[derive(CppMirror)]
+    #[cpp_mirror("name")]
     #[repr(C)]
     struct Name {
         first_name: StlString,
         last_name: StlString,
     }
 
+    #[derive(CppMirror)]
+    #[cpp_mirror("subclass")]
     #[repr(C)]
     struct Subclass {
+        // `superclass`, which has no member to check the offset of.
+        #[cpp_mirror(skip)]
         parent: i32,
         value: i32,
         desc: StlString,
     }
 
+    #[derive(CppMirror)]
+    #[cpp_mirror("struct_with_vectors")]
     #[repr(C)]
     struct StructWithVectors {
         ids: VectorOfI32,
@@ -598,4 +1534,203 @@ mod tests {
         x.ages.assign(vec![8, 7]);
         assert_eq!(x.ages.as_slice(), &[8, 7]);
     }
+
+    #[repr(C)]
+    struct StructWithStringVectors {
+        tags: VectorOfString,
+    }
+
+    #[test]
+    fn unittest_vector_of_string() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithStringVectors as "struct_with_string_vectors*" {
+                static struct_with_string_vectors x{{"rust", "c++", "python"}};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.tags.len(), 3);
+        assert_eq!(x.tags[1].to_rust(), "c++");
+        assert_eq!(x.tags.join(", "), "rust, c++, python");
+
+        x.tags.push_back("go");
+        x.tags.insert(0, "zig");
+        x.tags.set(2, "cpp");
+        assert_eq!(x.tags.remove(1), "rust");
+        x.tags.retain(|_, tag| tag.len() > 2);
+        assert_eq!(format!("{:?}", x.tags), r#"["zig", "cpp", "python"]"#);
+
+        let x: &StructWithStringVectors = x;
+        let len = unsafe {
+            cpp!([x as "const struct_with_string_vectors*"] -> size_t as "size_t" {
+                size_t len = 0;
+                for (const auto& tag : x->tags) {
+                    len = len * 10 + tag.size();
+                }
+                return len;
+            })
+        };
+        assert_eq!(len, 336);
+
+        let tags: Vec<String> = Vec::from(&x.tags);
+        let mut copy: Box<VectorOfString> = tags.clone().into();
+        assert_eq!(Vec::from(&*copy), tags);
+        copy.assign(vec!["a", "bb", "a long string beyond the small buffer"]);
+        copy.reverse();
+        copy.swap(0, 2);
+        copy.push_back("c");
+        assert_eq!(
+            copy.join("/"),
+            "a/bb/a long string beyond the small buffer/c"
+        );
+        copy.sort();
+        copy.push_back("d");
+        assert_eq!(
+            copy.join("/"),
+            "a/a long string beyond the small buffer/bb/c/d"
+        );
+        copy.clear();
+        assert!(copy.is_empty());
+    }
+
+    #[repr(C)]
+    struct StructWithNestedVectors {
+        tensor: VectorOfVector<VectorOfF32>,
+        adjacency: VectorOfVector<VectorOfI32>,
+    }
+
+    #[test]
+    fn unittest_vector_of_vector() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithNestedVectors as "struct_with_nested_vectors*" {
+                static struct_with_nested_vectors x{{{1, 2}, {3, 4}}, {{1, 2}, {2}, {}}};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.tensor.len(), 2);
+        assert_eq!(x.tensor[1].as_slice(), &[3.0, 4.0]);
+        assert_eq!(format!("{:?}", x.adjacency), "[[1, 2], [2], []]");
+
+        x.tensor[0][1] = 2.5;
+        x.tensor[1].push_back(5.0);
+        x.tensor.push_back(&[6.0]);
+        x.adjacency.insert(0, &[1, 2, 3]);
+        assert_eq!(x.adjacency.remove(3), Vec::<i32>::new());
+        x.adjacency[2].push_back(0);
+
+        let x: &StructWithNestedVectors = x;
+        let sum = unsafe {
+            cpp!([x as "const struct_with_nested_vectors*"] -> f32 as "float" {
+                float sum = 0;
+                for (const auto& row : x->tensor) {
+                    for (float value : row) {
+                        sum += value;
+                    }
+                }
+                return sum * 100 + x->adjacency.size() * 10 + x->adjacency[2].size();
+            })
+        };
+        assert_eq!(sum, 2150.0 + 30.0 + 2.0);
+
+        assert_eq!(
+            Vec::from(&x.adjacency),
+            vec![vec![1, 2, 3], vec![1, 2], vec![2, 0]]
+        );
+        let (values, offsets) = x.tensor.flatten();
+        assert_eq!(values, [1.0, 2.5, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(offsets, [0, 2, 5, 6]);
+
+        let mut copy: Box<VectorOfVector<VectorOfI32>> = Vec::from(&x.adjacency).into();
+        copy.retain(|_, inner| inner.len() > 2);
+        assert_eq!(Vec::from(&*copy), vec![vec![1, 2, 3]]);
+        copy.clear();
+        assert_eq!(copy.flatten(), (vec![], vec![0]));
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Point3f {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct BBox {
+        min: Point3f,
+        max: Point3f,
+        label: i32,
+    }
+
+    cpp_vector_of! {
+        Point3f: cpp_class!(unsafe struct Point3fLayout as "point3f");
+        BBox: cpp_class!(unsafe struct BBoxLayout as "bbox");
+    }
+
+    #[repr(C)]
+    struct StructWithPodVectors {
+        points: VectorOf<Point3f>,
+        boxes: VectorOf<BBox>,
+    }
+
+    fn point(x: f32, y: f32, z: f32) -> Point3f {
+        Point3f { x, y, z }
+    }
+
+    #[test]
+    fn unittest_vector_of_pod() {
+        let x = unsafe {
+            cpp!([] -> &mut StructWithPodVectors as "struct_with_pod_vectors*" {
+                static struct_with_pod_vectors x{{{1, 2, 3}}, {{{0, 0, 0}, {1, 1, 1}, 7}}};
+                return &x;
+            })
+        };
+
+        assert_eq!(x.points.as_slice(), &[point(1.0, 2.0, 3.0)]);
+        assert_eq!(x.boxes[0].label, 7);
+        assert_eq!(x.boxes[0].max, point(1.0, 1.0, 1.0));
+
+        x.points.extend((0..10).map(|i| point(i as f32, 0.0, 0.0)));
+        x.points.insert(0, point(-1.0, -1.0, -1.0));
+        assert_eq!(x.points.remove(1), point(1.0, 2.0, 3.0));
+        x.points.retain(|_, p| p.x as i32 % 3 != 1);
+        x.boxes[0].label = 8;
+        x.boxes.push_back(BBox {
+            min: point(2.0, 2.0, 2.0),
+            max: point(3.0, 4.0, 5.0),
+            label: 9,
+        });
+
+        let x: &mut StructWithPodVectors = x;
+        let (capacity, volume) = unsafe {
+            let mut capacity = 0usize;
+            let volume = cpp!([x as "struct_with_pod_vectors*", mut capacity as "size_t"] -> f32 as "float" {
+                capacity = x->points.capacity();
+                x->points.push_back({100, 0, 0});
+                x->points.shrink_to_fit();
+
+                float volume = 0;
+                for (const bbox& b : x->boxes) {
+                    volume += (b.max.x - b.min.x) * (b.max.y - b.min.y) * (b.max.z - b.min.z) * b.label;
+                }
+                return volume;
+            });
+            (capacity, volume)
+        };
+        assert_eq!((capacity, x.points.capacity()), (22, 9));
+        assert_eq!(volume, 8.0 + 6.0 * 9.0);
+
+        let xs: Vec<_> = x.points.iter().map(|p| p.x).collect();
+        assert_eq!(xs, [-1.0, 0.0, 2.0, 3.0, 5.0, 6.0, 8.0, 9.0, 100.0]);
+        x.points.truncate(2);
+        assert_eq!(x.points.len(), 2);
+
+        let mut boxes: Box<VectorOf<BBox>> = x.boxes.as_slice().into();
+        boxes.erase(0);
+        assert_eq!(Vec::from(&*boxes), &x.boxes[1..]);
+        boxes.clear();
+        assert!(boxes.is_empty());
+    }
 }